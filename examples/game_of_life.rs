@@ -1,3 +1,5 @@
+#![allow(clippy::println_empty_string)]
+
 use game_loop::game_loop;
 
 fn main() {
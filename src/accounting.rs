@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeAccounting {
+    Float,
+    Integer,
+}
+
+// In integer mode, time is counted in 'ticks' of 1/ticks_per_nano nanoseconds
+// so that steps such as 1/60th of a second can be represented exactly.
+#[derive(Copy, Clone)]
+pub(crate) struct Accumulator {
+    accounting: TimeAccounting,
+    seconds: f64,
+    ticks: u128,
+    step_seconds: f64,
+    step_ticks: u128,
+    rate: f64,
+    ticks_per_nano: u128,
+}
+
+impl Accumulator {
+    pub fn new(accounting: TimeAccounting, updates_per_second: u32) -> Self {
+        let mut accumulator = Self {
+            accounting,
+            seconds: 0.0,
+            ticks: 0,
+            step_seconds: 0.0,
+            step_ticks: 0,
+            rate: 0.0,
+            ticks_per_nano: 1,
+        };
+
+        accumulator.set_updates_per_second(updates_per_second);
+        accumulator
+    }
+
+    pub fn set_updates_per_second(&mut self, updates_per_second: u32) {
+        let rate = updates_per_second as f64;
+
+        // A rate of zero means no updates so the step can never be reached.
+        match updates_per_second {
+            0 => self.set_step(u128::MAX, 1, 1.0 / rate, rate),
+            _ => self.set_step(NANOS_PER_SECOND, updates_per_second as u128, 1.0 / rate, rate),
+        }
+    }
+
+    // Fractional rates are exact up to six decimal places, e.g. 59.94 Hz.
+    pub fn set_update_rate(&mut self, updates_per_second: f64) {
+        if updates_per_second <= 0.0 { return self.set_updates_per_second(0); }
+
+        let ticks_per_nano = (updates_per_second * 1e6).round().max(1.0) as u128;
+
+        self.set_step(NANOS_PER_SECOND * 1_000_000, ticks_per_nano, 1.0 / updates_per_second, updates_per_second);
+    }
+
+    // A step of zero would never be used up so the loop would update forever.
+    pub fn set_time_step(&mut self, time_step: Duration) {
+        assert!(!time_step.is_zero(), "the time step must be greater than zero");
+
+        let step_seconds = time_step.as_secs_f64();
+
+        self.set_step(time_step.as_nanos(), 1, step_seconds, 1.0 / step_seconds);
+    }
+
+    fn set_step(&mut self, step_ticks: u128, ticks_per_nano: u128, step_seconds: f64, rate: f64) {
+        self.ticks = self.ticks * ticks_per_nano / self.ticks_per_nano;
+        self.ticks_per_nano = ticks_per_nano;
+        self.step_ticks = step_ticks;
+        self.step_seconds = step_seconds;
+        self.rate = rate;
+    }
+
+    pub fn accounting(&self) -> TimeAccounting {
+        self.accounting
+    }
+
+    pub fn set_accounting(&mut self, accounting: TimeAccounting) {
+        match accounting {
+            TimeAccounting::Float => self.seconds = self.time(),
+            TimeAccounting::Integer => self.ticks = nanos(self.time()) as u128 * self.ticks_per_nano,
+        }
+
+        self.accounting = accounting;
+    }
+
+    pub fn add(&mut self, nanos: u64) {
+        match self.accounting {
+            TimeAccounting::Float => self.seconds += seconds(nanos),
+            TimeAccounting::Integer => self.ticks += nanos as u128 * self.ticks_per_nano,
+        }
+    }
+
+    pub fn has_step(&self) -> bool {
+        match self.accounting {
+            TimeAccounting::Float => self.seconds >= self.step_seconds,
+            TimeAccounting::Integer => self.ticks >= self.step_ticks,
+        }
+    }
+
+    pub fn consume_step(&mut self) {
+        match self.accounting {
+            TimeAccounting::Float => self.seconds -= self.step_seconds,
+            TimeAccounting::Integer => self.ticks -= self.step_ticks,
+        }
+    }
+
+    // Drops whole steps but keeps the fraction of a step that has accumulated.
+    pub fn drop_steps(&mut self) -> u64 {
+        match self.accounting {
+            TimeAccounting::Float => {
+                let dropped = self.seconds - self.seconds % self.step_seconds;
+                self.seconds -= dropped;
+                nanos(dropped)
+            },
+            TimeAccounting::Integer => {
+                let dropped = self.ticks - self.ticks % self.step_ticks;
+                self.ticks -= dropped;
+                (dropped / self.ticks_per_nano) as u64
+            },
+        }
+    }
+
+    pub fn time(&self) -> f64 {
+        match self.accounting {
+            TimeAccounting::Float => self.seconds,
+            TimeAccounting::Integer => self.ticks as f64 / (self.ticks_per_nano * NANOS_PER_SECOND) as f64,
+        }
+    }
+
+    // How long ago the next step was due, to the nanosecond. Rounding removes
+    // floating point error so that steps which coincide compare as equal.
+    pub fn overdue_nanos(&self) -> u64 {
+        match self.accounting {
+            TimeAccounting::Float => nanos((self.seconds - self.step_seconds).max(0.0)),
+            TimeAccounting::Integer => (self.ticks.saturating_sub(self.step_ticks) / self.ticks_per_nano) as u64,
+        }
+    }
+
+    pub fn step(&self) -> f64 {
+        self.step_seconds
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn blending_factor(&self) -> f64 {
+        match self.accounting {
+            TimeAccounting::Float => self.seconds / self.step_seconds,
+            TimeAccounting::Integer => self.ticks as f64 / self.step_ticks as f64,
+        }
+    }
+}
+
+pub(crate) fn seconds(nanos: u64) -> f64 {
+    nanos as f64 / NANOS_PER_SECOND as f64
+}
+
+pub(crate) fn nanos(seconds: f64) -> u64 {
+    (seconds * NANOS_PER_SECOND as f64).round() as u64
+}
@@ -0,0 +1,54 @@
+use crate::*;
+use std::future::Future;
+use std::time::Duration;
+
+// A clock that can sleep without blocking the thread of an async runtime.
+pub trait AsyncTimeTrait: TimeTrait {
+    fn sleep_async(&mut self, seconds: f64) -> impl Future<Output = ()>;
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct TokioTime;
+
+impl TimeTrait for TokioTime {
+    type Instant = tokio::time::Instant;
+
+    fn now(&self) -> Self::Instant {
+        tokio::time::Instant::now()
+    }
+
+    fn sub(&self, later: &Self::Instant, earlier: &Self::Instant) -> f64 {
+        later.duration_since(*earlier).as_secs_f64()
+    }
+
+    fn sub_nanos(&self, later: &Self::Instant, earlier: &Self::Instant) -> u64 {
+        later.duration_since(*earlier).as_nanos() as u64
+    }
+
+    // Sleeping inside next_frame would block the runtime's thread so
+    // next_frame_async sleeps between frames instead.
+    fn supports_sleep(&self) -> bool {
+        false
+    }
+
+    fn sleep(&mut self, seconds: f64) {
+        std::thread::sleep(Duration::from_secs_f64(seconds));
+    }
+}
+
+impl AsyncTimeTrait for TokioTime {
+    fn sleep_async(&mut self, seconds: f64) -> impl Future<Output = ()> {
+        tokio::time::sleep(Duration::from_secs_f64(seconds))
+    }
+}
+
+pub async fn game_loop_async<G, U, R>(game: G, updates_per_second: u32, max_frame_time: f64, mut update: U, mut render: R) -> GameLoop<G, TokioTime, ()>
+    where U: AsyncFnMut(&mut GameLoop<G, TokioTime, ()>),
+          R: FnMut(&mut GameLoop<G, TokioTime, ()>),
+{
+    let mut game_loop = GameLoop::with_clock(game, updates_per_second, max_frame_time, (), TokioTime);
+
+    while game_loop.frame_async(&mut update, &mut render).await {}
+
+    game_loop
+}
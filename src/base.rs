@@ -1,43 +1,138 @@
 use crate::*;
+use crate::trace::{trace_event, trace_span};
+#[cfg(feature = "tokio")]
+use crate::trace::trace_instrument;
+use std::time::Duration;
+
+// Callbacks are Send so that the game loop can be moved to another thread
+// whenever the game, clock and window can be.
+pub(crate) type Callback<G, T, W> = Box<dyn FnMut(&mut GameLoop<G, T, W>) + Send>;
 
 pub struct GameLoop<G, T: TimeTrait, W> {
     pub game: G,
+    // Rounded to a whole number so it's only approximate for fractional rates
+    // and time steps, e.g. 0 for 0.4 Hz. Use update_rate() instead.
+    #[deprecated(note = "rounded to a whole number, use update_rate() instead")]
     pub updates_per_second: u32,
     pub max_frame_time: f64,
+    pub max_renders_per_second: Option<u32>,
+    pub pacing: PacingMode,
+    pub time_scale: f64,
+    pub catch_up_policy: CatchUpPolicy,
+    pub loop_mode: LoopMode,
+    pub render_timing: RenderTiming,
     pub exit_next_iteration: bool,
     pub window: W,
     pub window_occluded: bool,
+    pub measure_phases: bool,
+    pub clock: T,
 
-    fixed_time_step: f64,
+    accumulator: Accumulator,
+    pacer: Pacer,
     number_of_updates: u64,
     number_of_renders: u64,
-    last_frame_time: f64,
-    running_time: f64,
-    accumulated_time: f64,
+    last_frame_nanos: u64,
+    running_nanos: u64,
+    game_nanos: u64,
+    discarded_nanos: u64,
+    catch_up_scale: f64,
     blending_factor: f64,
-    previous_instant: T,
-    current_instant: T,
+    delta_time: f64,
+    paused: bool,
+    pending_updates: u64,
+    pending_frame: bool,
+    channels: Vec<UpdateChannel>,
+    channel_callbacks: Vec<Option<Callback<G, T, W>>>,
+    next_channel_id: u64,
+    scheduler: Scheduler<G, T, W>,
+    simulated_nanos: u64,
+    stats: FrameStats,
+    last_update_nanos: u64,
+    update_nanos: u64,
+    render_nanos: u64,
+    sleep_nanos: u64,
+    chrome_trace: Option<ChromeTrace>,
+    previous_game: Option<G>,
+    previous_instant: T::Instant,
+    current_instant: T::Instant,
 }
 
-impl<G, T: TimeTrait, W> GameLoop<G, T, W> {
+impl<G, T: TimeTrait + Default, W> GameLoop<G, T, W> {
     pub fn new(game: G, updates_per_second: u32, max_frame_time: f64, window: W) -> Self {
+        Self::with_clock(game, updates_per_second, max_frame_time, window, T::default())
+    }
+
+    pub fn with_update_rate(game: G, updates_per_second: f64, max_frame_time: f64, window: W) -> Self {
+        let mut game_loop = Self::new(game, 1, max_frame_time, window);
+        game_loop.set_update_rate(updates_per_second);
+        game_loop
+    }
+
+    pub fn with_time_step(game: G, time_step: Duration, max_frame_time: f64, window: W) -> Self {
+        let mut game_loop = Self::new(game, 1, max_frame_time, window);
+        game_loop.set_time_step(time_step);
+        game_loop
+    }
+}
+
+impl<G, T: TimeTrait, W> GameLoop<G, T, W> {
+    #[allow(deprecated)]
+    pub fn with_clock(game: G, updates_per_second: u32, max_frame_time: f64, window: W, clock: T) -> Self {
+        // Nothing here allocates but the first call into each constructor can
+        // still take a few microseconds, so read the clock last to keep that
+        // out of the first frame.
+        let accumulator = Accumulator::new(TimeAccounting::Float, updates_per_second);
+        let pacer = Pacer::default();
+        let channels = vec![];
+        let channel_callbacks = vec![];
+        let scheduler = Scheduler::new();
+        let stats = FrameStats::new(120);
+        let now = clock.now();
+
         Self {
             game,
             updates_per_second,
             max_frame_time,
+            max_renders_per_second: None,
+            pacing: PacingMode::Sleep,
+            time_scale: 1.0,
+            catch_up_policy: CatchUpPolicy::Unlimited,
+            loop_mode: LoopMode::Fixed,
+            render_timing: RenderTiming::Interpolate,
             window,
             window_occluded: false,
+            measure_phases: false,
             exit_next_iteration: false,
+            clock,
 
-            fixed_time_step: 1.0 / updates_per_second as f64,
+            accumulator,
+            pacer,
             number_of_updates: 0,
             number_of_renders: 0,
-            running_time: 0.0,
-            accumulated_time: 0.0,
+            running_nanos: 0,
+            game_nanos: 0,
+            discarded_nanos: 0,
+            catch_up_scale: 1.0,
             blending_factor: 0.0,
-            previous_instant: T::now(),
-            current_instant: T::now(),
-            last_frame_time: 0.0,
+            delta_time: 0.0,
+            paused: false,
+            pending_updates: 0,
+            pending_frame: false,
+            channels,
+            channel_callbacks,
+            next_channel_id: 0,
+            scheduler,
+            simulated_nanos: 0,
+            stats,
+            previous_instant: now,
+            current_instant: now,
+            last_frame_nanos: 0,
+            last_update_nanos: 0,
+            update_nanos: 0,
+            render_nanos: 0,
+            sleep_nanos: 0,
+            chrome_trace: None,
+            previous_game: None,
         }
     }
 
@@ -49,66 +144,505 @@ impl<G, T: TimeTrait, W> GameLoop<G, T, W> {
 
         if g.exit_next_iteration { return false; }
 
-        g.current_instant = T::now();
+        let measured = g.measure_frame();
+        let _span = trace_span!("next_frame", frame_time = seconds(measured));
 
-        let mut elapsed = g.current_instant.sub(&g.previous_instant);
-        if elapsed > g.max_frame_time { elapsed = g.max_frame_time; }
+        let mut frame = g.begin_frame(measured);
 
-        g.last_frame_time = elapsed;
-        g.running_time += elapsed;
-        g.accumulated_time += elapsed;
+        while g.start_update(&frame) {
+            g.timed_update(&mut update);
+            g.end_update(&mut frame);
+        }
 
-        while g.accumulated_time >= g.fixed_time_step {
-            update(g);
+        g.end_updates(&frame);
+        g.channel_updates(frame.frame_nanos, frame.step_frame);
 
-            g.accumulated_time -= g.fixed_time_step;
-            g.number_of_updates += 1;
+        for _ in 0..g.start_steps(&frame) {
+            g.timed_update(&mut update);
+            g.end_step();
         }
 
-        g.blending_factor = g.accumulated_time / g.fixed_time_step;
+        g.end_frame(frame, &mut render);
 
-        if g.window_occluded && T::supports_sleep() {
-            T::sleep(g.fixed_time_step);
-        } else {
-            render(g);
-            g.number_of_renders += 1;
+        true
+    }
+
+    fn measure_frame(&mut self) -> u64 {
+        self.current_instant = self.clock.now();
+        self.clock.sub_nanos(&self.current_instant, &self.previous_instant)
+    }
+
+    fn begin_frame(&mut self, measured: u64) -> FrameState<T::Instant> {
+        let g = self;
+
+        let frame_start = g.current_instant;
+        let wall_start = g.phase_instant();
+
+        let elapsed = measured.min(nanos(g.max_frame_time));
+
+        if measured > elapsed {
+            trace_event!(debug, frame_time = seconds(measured), max_frame_time = g.max_frame_time, "frame time was clamped");
         }
 
-        g.previous_instant = g.current_instant;
+        g.last_frame_nanos = elapsed;
+        g.running_nanos += elapsed;
+        g.discarded_nanos = 0;
+
+        // Steps only apply to the frame after they were requested while paused.
+        let step_updates = std::mem::take(&mut g.pending_updates);
+        let step_frame = std::mem::take(&mut g.pending_frame) && g.paused;
+
+        let mut frame_nanos = 0;
+
+        if !g.paused || step_frame {
+            frame_nanos = scale(elapsed, g.time_scale * g.catch_up_scale);
+
+            g.discarded_nanos = scale(measured, g.time_scale).saturating_sub(frame_nanos);
+            g.game_nanos += frame_nanos;
+        }
+
+        if g.loop_mode == LoopMode::Fixed {
+            g.accumulator.add(frame_nanos);
+            g.delta_time = g.accumulator.step();
+        }
+
+        FrameState {
+            frame_start,
+            wall_start,
+            measured,
+            elapsed,
+            updates_before: g.number_of_updates,
+            loop_mode: g.loop_mode,
+            max_updates: g.catch_up_policy.max_updates(),
+            step_nanos: nanos(g.accumulator.step()),
+            frame_nanos,
+            remaining_nanos: frame_nanos,
+            updates_this_frame: 0,
+            step_frame,
+            step_updates,
+        }
+    }
+
+    // Sets the delta time and returns true if another update is due this frame.
+    fn start_update(&mut self, frame: &FrameState<T::Instant>) -> bool {
+        let g = self;
+
+        let updating = !g.paused || frame.step_frame;
+        let below_max = frame.updates_this_frame < frame.max_updates;
+
+        let due = match frame.loop_mode {
+            LoopMode::Fixed => updating && g.accumulator.has_step() && below_max,
+            LoopMode::Variable => frame.frame_nanos > 0 && frame.updates_this_frame == 0,
+            LoopMode::SemiFixed => updating && frame.remaining_nanos > 0 && below_max,
+        };
+
+        if !due { return false; }
+
+        g.delta_time = match frame.loop_mode {
+            LoopMode::Fixed => g.accumulator.step(),
+            LoopMode::Variable => seconds(frame.frame_nanos),
+            LoopMode::SemiFixed => seconds(frame.remaining_nanos.min(frame.step_nanos)),
+        };
 
         true
     }
 
+    fn end_update(&mut self, frame: &mut FrameState<T::Instant>) {
+        let g = self;
+
+        match frame.loop_mode {
+            LoopMode::Fixed => {
+                g.accumulator.consume_step();
+                g.finish_update(nanos(g.accumulator.time()));
+            },
+            LoopMode::Variable => g.finish_update(0),
+            LoopMode::SemiFixed => {
+                frame.remaining_nanos -= frame.remaining_nanos.min(frame.step_nanos);
+                g.finish_update(frame.remaining_nanos);
+            },
+        }
+
+        frame.updates_this_frame += 1;
+    }
+
+    // Semi-fixed time left over after max_updates is discarded rather than
+    // carried forward.
+    fn end_updates(&mut self, frame: &FrameState<T::Instant>) {
+        match frame.loop_mode {
+            LoopMode::Fixed => {
+                let fell_behind = frame.updates_this_frame == frame.max_updates && self.accumulator.has_step();
+                self.catch_up(fell_behind);
+            },
+            LoopMode::Variable => {},
+            LoopMode::SemiFixed => {
+                self.game_nanos -= frame.remaining_nanos;
+                self.discarded_nanos += frame.remaining_nanos;
+            },
+        }
+    }
+
+    // Returns how many steps were requested while paused. Channels may have
+    // resumed the game in which case the steps are no longer needed.
+    fn start_steps(&mut self, frame: &FrameState<T::Instant>) -> u64 {
+        if !self.paused { return 0; }
+
+        self.delta_time = self.accumulator.step();
+        frame.step_updates
+    }
+
+    fn end_step(&mut self) {
+        self.game_nanos += nanos(self.accumulator.step());
+        self.finish_update(nanos(self.accumulator.time()));
+    }
+
+    fn end_frame<R>(&mut self, frame: FrameState<T::Instant>, render: &mut R)
+        where R: FnMut(&mut GameLoop<G, T, W>),
+    {
+        let g = self;
+        let FrameState { frame_start, wall_start, measured, elapsed, updates_before, .. } = frame;
+
+        g.blending_factor = g.accumulator.blending_factor();
+
+        let update_end = g.phase_instant();
+        g.update_nanos = phase_nanos(wall_start, update_end);
+
+        let rendered = !(g.window_occluded && g.clock.supports_sleep());
+        let mut render_end = update_end;
+        let mut slept = !rendered;
+
+        if rendered {
+            {
+                let _span = trace_span!("render", blending_factor = g.blending_factor, frame_time = seconds(elapsed));
+                render(g);
+            }
+
+            g.number_of_renders += 1;
+
+            render_end = g.phase_instant();
+            slept = g.wait_for_next_render(&frame_start);
+        } else {
+            trace_event!(debug, sleep_time = g.accumulator.step(), "window is occluded so sleeping instead of rendering");
+            g.clock.sleep(g.accumulator.step());
+        }
+
+        let wall_end = g.phase_instant();
+
+        g.render_nanos = phase_nanos(update_end, render_end);
+        g.sleep_nanos = phase_nanos(render_end, wall_end);
+
+        // The trace may have been started part way through the frame.
+        let instants = (wall_start, update_end, render_end, wall_end);
+
+        if let (Some(trace), (Some(wall_start), Some(update_end), Some(render_end), Some(wall_end))) = (g.chrome_trace.as_mut(), instants) {
+            if measured > elapsed {
+                trace.marker("clamped_frame", &wall_start, &[("frame_time", seconds(measured)), ("max_frame_time", g.max_frame_time)]);
+            }
+
+            trace.slice("frame", &wall_start, &wall_end, &[("frame_time", seconds(measured))]);
+
+            if rendered { trace.slice("render", &update_end, &render_end, &[("blending_factor", g.blending_factor)]); }
+            if slept { trace.slice("sleep", &render_end, &wall_end, &[]); }
+        }
+
+        g.stats.record(Frame {
+            frame_time: seconds(measured),
+            updates: g.number_of_updates - updates_before,
+            rendered,
+            update_time: seconds(g.update_nanos),
+            render_time: seconds(g.render_nanos),
+            sleep_time: seconds(g.sleep_nanos),
+        });
+
+        g.previous_instant = g.current_instant;
+    }
+
     pub fn re_accumulate(&mut self) {
         let g = self;
 
-        g.current_instant = T::now();
+        // Other loop modes don't blend so the time is left for the next frame.
+        if g.loop_mode != LoopMode::Fixed { return; }
+
+        g.current_instant = g.clock.now();
 
-        let prev_elapsed = g.last_frame_time;
-        let new_elapsed = g.current_instant.sub(&g.previous_instant);
+        let prev_elapsed = g.last_frame_nanos;
+        let new_elapsed = g.clock.sub_nanos(&g.current_instant, &g.previous_instant);
 
-        let delta = new_elapsed - prev_elapsed;
+        let delta = new_elapsed.saturating_sub(prev_elapsed);
 
         // We don't update g.last_frame_time since this additional time in the
         // render function is considered part of the current frame.
 
-        g.running_time += delta;
-        g.accumulated_time += delta;
+        g.running_nanos += delta;
 
-        g.blending_factor = g.accumulated_time / g.fixed_time_step;
+        if !g.paused {
+            let scaled = scale(delta, g.time_scale * g.catch_up_scale);
+
+            g.game_nanos += scaled;
+            g.accumulator.add(scaled);
+        }
+
+        g.blending_factor = g.accumulator.blending_factor();
+    }
+
+    fn timed_update<U>(&mut self, update: &mut U)
+        where U: FnMut(&mut GameLoop<G, T, W>),
+    {
+        let _span = trace_span!("update", update = self.number_of_updates, delta_time = self.delta_time);
+
+        let start = self.phase_instant();
+        update(self);
+        self.record_update_time(start);
+    }
+
+    fn record_update_time(&mut self, start: Option<WallInstant>) {
+        let end = self.phase_instant();
+
+        self.last_update_nanos = phase_nanos(start, end);
+
+        if let (Some(trace), Some(start), Some(end)) = (self.chrome_trace.as_mut(), start, end) {
+            trace.slice("update", &start, &end, &[("update", self.number_of_updates as f64), ("delta_time", self.delta_time)]);
+        }
+    }
+
+    // Phases are timed with the wall clock so that they're meaningful even if
+    // the game's clock is manual. It's only read when phases are measured or
+    // traced so that loops with their own clock never touch it otherwise.
+    fn phase_instant(&self) -> Option<WallInstant> {
+        if self.measure_phases || self.chrome_trace.is_some() { Some(Time.now()) } else { None }
+    }
+
+    // Timers are keyed to game time that has been simulated, i.e. excluding
+    // time that has accumulated but not yet been consumed by an update.
+    fn finish_update(&mut self, unsimulated_nanos: u64) {
+        let g = self;
+
+        g.number_of_updates += 1;
+        g.simulated_nanos = g.game_nanos.saturating_sub(unsimulated_nanos);
+
+        while let Some(mut timer) = g.scheduler.take_due(g.simulated_nanos, g.number_of_updates) {
+            (timer.callback)(g);
+            g.scheduler.finish(timer, g.simulated_nanos, g.number_of_updates);
+        }
+
+        g.scheduler.finish_update();
+    }
+
+    // Runs whichever channel's update was due the longest time ago. Channels
+    // that are due at exactly the same time run in the order they were added.
+    fn channel_updates(&mut self, frame_nanos: u64, step_frame: bool) {
+        let g = self;
+
+        for channel in g.channels.iter_mut() {
+            channel.accumulator().add(frame_nanos);
+        }
+
+        while !g.paused || step_frame {
+            let mut next: Option<(usize, u64)> = None;
+
+            for (index, channel) in g.channels.iter_mut().enumerate() {
+                let accumulator = channel.accumulator();
+                if !accumulator.has_step() { continue; }
+
+                let overdue = accumulator.overdue_nanos();
+
+                match next {
+                    Some((_, most_overdue)) if most_overdue >= overdue => {},
+                    _ => next = Some((index, overdue)),
+                }
+            }
+
+            let index = match next { Some((index, _)) => index, None => break };
+            let id = g.channels[index].id;
+
+            let mut callback = g.channel_callbacks[index].take();
+            g.delta_time = g.channels[index].fixed_time_step();
+
+            if let Some(callback) = callback.as_mut() { callback(g); }
+
+            // The callback may have added or removed channels.
+            if let Some(index) = g.channels.iter().position(|c| c.id == id) {
+                g.channel_callbacks[index] = callback;
+                g.channels[index].finish_update();
+            }
+        }
+
+        for channel in g.channels.iter_mut() {
+            channel.finish_frame();
+        }
+    }
+
+    fn catch_up(&mut self, fell_behind: bool) {
+        let dropped = match self.catch_up_policy {
+            CatchUpPolicy::DropRemainder { .. } if fell_behind => self.accumulator.drop_steps(),
+            CatchUpPolicy::SlowDown { slow_down, min_scale, .. } if fell_behind => {
+                self.catch_up_scale = (self.catch_up_scale * slow_down).max(min_scale);
+                0
+            },
+            CatchUpPolicy::SlowDown { recovery, .. } => {
+                self.catch_up_scale = (self.catch_up_scale + recovery).min(1.0);
+                0
+            },
+            _ => {
+                self.catch_up_scale = 1.0;
+                0
+            },
+        };
+
+        self.game_nanos -= dropped;
+        self.discarded_nanos += dropped;
+    }
+
+    fn wait_for_next_render(&mut self, frame_start: &T::Instant) -> bool {
+        let max_renders_per_second = match self.max_renders_per_second {
+            // A cap of zero would mean never rendering so it's ignored instead.
+            Some(max) if max > 0 && self.clock.supports_sleep() => max,
+            _ => return false,
+        };
+
+        let render_time = self.clock.sub(&self.clock.now(), frame_start);
+        let remaining = 1.0 / max_renders_per_second as f64 - render_time;
+
+        if remaining > 0.0 { self.pacer.wait(self.pacing, &mut self.clock, remaining); }
+
+        remaining > 0.0
+    }
+
+    pub fn add_channel<F>(&mut self, name: &str, updates_per_second: u32, callback: F)
+        where F: FnMut(&mut GameLoop<G, T, W>) + Send + 'static,
+    {
+        let accounting = self.accumulator.accounting();
+        let id = self.next_channel_id;
+
+        self.remove_channel(name);
+        self.channels.push(UpdateChannel::new(id, name, accounting, updates_per_second));
+        self.channel_callbacks.push(Some(Box::new(callback)));
+        self.next_channel_id += 1;
+    }
+
+    pub fn remove_channel(&mut self, name: &str) {
+        if let Some(index) = self.channels.iter().position(|c| c.name() == name) {
+            self.channels.remove(index);
+            self.channel_callbacks.remove(index);
+        }
+    }
+
+    pub fn channel(&self, name: &str) -> Option<&UpdateChannel> {
+        self.channels.iter().find(|c| c.name() == name)
+    }
+
+    pub fn channel_mut(&mut self, name: &str) -> Option<&mut UpdateChannel> {
+        self.channels.iter_mut().find(|c| c.name() == name)
+    }
+
+    pub fn schedule_after<F>(&mut self, delay: f64, callback: F) -> TimerId
+        where F: FnMut(&mut GameLoop<G, T, W>) + Send + 'static,
+    {
+        self.scheduler.at(self.simulated_nanos + nanos(delay), Box::new(callback))
+    }
+
+    pub fn schedule_at<F>(&mut self, game_time: f64, callback: F) -> TimerId
+        where F: FnMut(&mut GameLoop<G, T, W>) + Send + 'static,
+    {
+        self.scheduler.at(nanos(game_time), Box::new(callback))
+    }
+
+    pub fn schedule_every<F>(&mut self, interval: f64, callback: F) -> TimerId
+        where F: FnMut(&mut GameLoop<G, T, W>) + Send + 'static,
+    {
+        self.scheduler.every(self.simulated_nanos, nanos(interval), Box::new(callback))
+    }
+
+    pub fn schedule_every_updates<F>(&mut self, interval: u64, callback: F) -> TimerId
+        where F: FnMut(&mut GameLoop<G, T, W>) + Send + 'static,
+    {
+        self.scheduler.every_updates(self.number_of_updates, interval, Box::new(callback))
+    }
+
+    pub fn cancel_timer(&mut self, id: TimerId) -> bool {
+        self.scheduler.cancel(id)
+    }
+
+    pub fn is_scheduled(&self, id: TimerId) -> bool {
+        self.scheduler.is_scheduled(id)
+    }
+
+    // Records frames until the trace is stopped, e.g. to save it to a file.
+    pub fn start_chrome_trace(&mut self) {
+        self.chrome_trace = Some(ChromeTrace::new());
+    }
+
+    pub fn stop_chrome_trace(&mut self) -> Option<ChromeTrace> {
+        self.chrome_trace.take()
+    }
+
+    pub fn chrome_trace(&self) -> Option<&ChromeTrace> {
+        self.chrome_trace.as_ref()
     }
 
     pub fn exit(&mut self) {
         self.exit_next_iteration = true;
     }
 
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn step_updates(&mut self, number_of_updates: u64) {
+        self.pending_updates += number_of_updates;
+    }
+
+    pub fn step_frame(&mut self) {
+        self.pending_frame = true;
+    }
+
+    #[allow(deprecated)]
     pub fn set_updates_per_second(&mut self, new_updates_per_second: u32) {
         self.updates_per_second = new_updates_per_second;
-        self.fixed_time_step = 1.0 / new_updates_per_second as f64;
+        self.accumulator.set_updates_per_second(new_updates_per_second);
+    }
+
+    #[allow(deprecated)]
+    pub fn set_update_rate(&mut self, new_updates_per_second: f64) {
+        self.updates_per_second = new_updates_per_second.round() as u32;
+        self.accumulator.set_update_rate(new_updates_per_second);
+    }
+
+    #[allow(deprecated)]
+    pub fn set_time_step(&mut self, new_time_step: Duration) {
+        self.accumulator.set_time_step(new_time_step);
+        self.updates_per_second = (1.0 / new_time_step.as_secs_f64()).round() as u32;
+    }
+
+    pub fn set_time_accounting(&mut self, accounting: TimeAccounting) {
+        self.accumulator.set_accounting(accounting);
+
+        for channel in self.channels.iter_mut() {
+            channel.accumulator().set_accounting(accounting);
+        }
+    }
+
+    pub fn time_accounting(&self) -> TimeAccounting {
+        self.accumulator.accounting()
+    }
+
+    pub fn update_rate(&self) -> f64 {
+        self.accumulator.rate()
     }
 
     pub fn fixed_time_step(&self) -> f64 {
-        self.fixed_time_step
+        self.accumulator.step()
+    }
+
+    pub fn spin_threshold(&self) -> f64 {
+        self.pacer.spin_threshold(self.pacing)
     }
 
     pub fn number_of_updates(&self) -> u64 {
@@ -120,26 +654,214 @@ impl<G, T: TimeTrait, W> GameLoop<G, T, W> {
     }
 
     pub fn last_frame_time(&self) -> f64 {
-        self.last_frame_time
+        seconds(self.last_frame_nanos)
     }
 
     pub fn running_time(&self) -> f64 {
-        self.running_time
+        seconds(self.running_nanos)
+    }
+
+    pub fn game_time(&self) -> f64 {
+        seconds(self.game_nanos)
+    }
+
+    pub fn discarded_time(&self) -> f64 {
+        seconds(self.discarded_nanos)
+    }
+
+    pub fn catch_up_scale(&self) -> f64 {
+        self.catch_up_scale
     }
 
     pub fn accumulated_time(&self) -> f64 {
-        self.accumulated_time
+        self.accumulator.time()
     }
 
     pub fn blending_factor(&self) -> f64 {
         self.blending_factor
     }
 
-    pub fn previous_instant(&self) -> T {
+    // The time since the state of the most recent update, i.e. how far to
+    // extrapolate forward from it.
+    pub fn extrapolation_time(&self) -> f64 {
+        self.blending_factor * self.accumulator.step()
+    }
+
+    // Predicts a value from its velocity at the time that render is showing.
+    // When interpolating, this is one update behind the most recent state.
+    pub fn predict<V: Extrapolate>(&self, value: &V, velocity: &V) -> V {
+        let seconds = match self.render_timing {
+            RenderTiming::Interpolate => self.extrapolation_time() - self.accumulator.step(),
+            RenderTiming::Extrapolate => self.extrapolation_time(),
+        };
+
+        value.extrapolate(velocity, seconds)
+    }
+
+    pub fn delta_time(&self) -> f64 {
+        self.delta_time
+    }
+
+    pub fn last_update_time(&self) -> f64 {
+        seconds(self.last_update_nanos)
+    }
+
+    // Includes channels and timers as well as calls to the update function.
+    pub fn update_time(&self) -> f64 {
+        seconds(self.update_nanos)
+    }
+
+    pub fn render_time(&self) -> f64 {
+        seconds(self.render_nanos)
+    }
+
+    pub fn sleep_time(&self) -> f64 {
+        seconds(self.sleep_nanos)
+    }
+
+    pub fn stats(&self) -> &FrameStats {
+        &self.stats
+    }
+
+    pub fn stats_mut(&mut self) -> &mut FrameStats {
+        &mut self.stats
+    }
+
+    pub fn previous_instant(&self) -> T::Instant {
         self.previous_instant
     }
 
-    pub fn current_instant(&self) -> T {
+    pub fn current_instant(&self) -> T::Instant {
         self.current_instant
     }
 }
+
+#[cfg(feature = "tokio")]
+impl<G, T: AsyncTimeTrait, W> GameLoop<G, T, W> {
+    // Works like next_frame but awaits each update in place. The loop sleeps
+    // between frames rather than inside them so the runtime isn't blocked.
+    pub async fn next_frame_async<U, R>(&mut self, mut update: U, mut render: R) -> bool
+        where U: AsyncFnMut(&mut GameLoop<G, T, W>),
+              R: FnMut(&mut GameLoop<G, T, W>),
+    {
+        self.frame_async(&mut update, &mut render).await
+    }
+
+    // Takes the closures by reference rather than passing &mut U as the closure
+    // so the future stays Send and can be spawned onto a runtime.
+    pub(crate) async fn frame_async<U, R>(&mut self, update: &mut U, render: &mut R) -> bool
+        where U: AsyncFnMut(&mut GameLoop<G, T, W>),
+              R: FnMut(&mut GameLoop<G, T, W>),
+    {
+        if self.exit_next_iteration { return false; }
+
+        let measured = self.measure_frame();
+
+        trace_instrument!(async {
+            let mut frame = self.begin_frame(measured);
+
+            while self.start_update(&frame) {
+                self.timed_update_async(update).await;
+                self.end_update(&mut frame);
+            }
+
+            self.end_updates(&frame);
+            self.channel_updates(frame.frame_nanos, frame.step_frame);
+
+            for _ in 0..self.start_steps(&frame) {
+                self.timed_update_async(update).await;
+                self.end_step();
+            }
+
+            self.end_frame(frame, render);
+        }, "next_frame", frame_time = seconds(measured)).await;
+
+        let remaining = match self.max_renders_per_second {
+            Some(max) if max > 0 => 1.0 / max as f64 - self.clock.sub(&self.clock.now(), &self.current_instant),
+            _ => self.time_until_next_update(),
+        };
+
+        // Sleeping past the maximum frame time gains nothing since the frame
+        // would be clamped. It also keeps the sleep finite if nothing is due.
+        let remaining = remaining.min(self.max_frame_time);
+
+        if remaining > 0.0 && remaining.is_finite() && !self.exit_next_iteration { self.clock.sleep_async(remaining).await; }
+
+        true
+    }
+
+    async fn timed_update_async<U>(&mut self, update: &mut U)
+        where U: AsyncFnMut(&mut GameLoop<G, T, W>),
+    {
+        let start = self.phase_instant();
+        trace_instrument!((*update)(self), "update", update = self.number_of_updates, delta_time = self.delta_time).await;
+        self.record_update_time(start);
+    }
+
+    fn time_until_next_update(&self) -> f64 {
+        let rate = self.time_scale * self.catch_up_scale;
+
+        if self.paused || rate <= 0.0 || self.loop_mode != LoopMode::Fixed {
+            return self.accumulator.step();
+        }
+
+        (self.accumulator.step() - self.accumulator.time()) / rate
+    }
+}
+
+impl<G: Clone, T: TimeTrait, W> GameLoop<G, T, W> {
+    // Keeps a copy of the game from before the most recent update so that
+    // render can blend between the previous and current states. The game is
+    // only cloned on frames that have updates.
+    pub fn next_frame_with_previous<U, R>(&mut self, mut update: U, mut render: R) -> bool
+        where U: FnMut(&mut GameLoop<G, T, W>),
+              R: FnMut(&G, &G, f64),
+    {
+        self.next_frame(|g| {
+            match g.previous_game.as_mut() {
+                Some(previous) => previous.clone_from(&g.game),
+                None => g.previous_game = Some(g.game.clone()),
+            }
+
+            update(g);
+        }, |g| {
+            let previous = g.previous_game.as_ref().unwrap_or(&g.game);
+            render(previous, &g.game, g.blending_factor);
+        })
+    }
+
+    pub fn previous_game(&self) -> Option<&G> {
+        self.previous_game.as_ref()
+    }
+}
+
+// Where a frame has got to between its updates so that next_frame_async can
+// await each update in place.
+struct FrameState<I> {
+    frame_start: I,
+    wall_start: Option<WallInstant>,
+    measured: u64,
+    elapsed: u64,
+    updates_before: u64,
+    loop_mode: LoopMode,
+    max_updates: u64,
+    step_nanos: u64,
+    frame_nanos: u64,
+    remaining_nanos: u64,
+    updates_this_frame: u64,
+    step_frame: bool,
+    step_updates: u64,
+}
+
+fn phase_nanos(start: Option<WallInstant>, end: Option<WallInstant>) -> u64 {
+    match (start, end) {
+        (Some(start), Some(end)) => Time.sub_nanos(&end, &start),
+        _ => 0,
+    }
+}
+
+fn scale(nanos: u64, time_scale: f64) -> u64 {
+    if time_scale == 1.0 { return nanos; }
+
+    (nanos as f64 * time_scale).round() as u64
+}
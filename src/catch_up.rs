@@ -0,0 +1,21 @@
+// SlowDown keeps the remainder like KeepRemainder but also scales game time
+// by slow_down each frame that falls behind, down to min_scale, so that the
+// backlog shrinks. The scale then recovers by recovery per frame that keeps up.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CatchUpPolicy {
+    Unlimited,
+    KeepRemainder { max_updates: u32 },
+    DropRemainder { max_updates: u32 },
+    SlowDown { max_updates: u32, slow_down: f64, min_scale: f64, recovery: f64 },
+}
+
+impl CatchUpPolicy {
+    pub(crate) fn max_updates(&self) -> u64 {
+        match *self {
+            CatchUpPolicy::Unlimited => u64::MAX,
+            CatchUpPolicy::KeepRemainder { max_updates } => max_updates as u64,
+            CatchUpPolicy::DropRemainder { max_updates } => max_updates as u64,
+            CatchUpPolicy::SlowDown { max_updates, .. } => max_updates as u64,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::*;
+use std::time::Duration;
+
+pub struct UpdateChannel {
+    pub(crate) id: u64,
+    name: String,
+    accumulator: Accumulator,
+    number_of_updates: u64,
+    blending_factor: f64,
+}
+
+impl UpdateChannel {
+    pub(crate) fn new(id: u64, name: &str, accounting: TimeAccounting, updates_per_second: u32) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            accumulator: Accumulator::new(accounting, updates_per_second),
+            number_of_updates: 0,
+            blending_factor: 0.0,
+        }
+    }
+
+    pub(crate) fn accumulator(&mut self) -> &mut Accumulator {
+        &mut self.accumulator
+    }
+
+    pub(crate) fn finish_update(&mut self) {
+        self.accumulator.consume_step();
+        self.number_of_updates += 1;
+    }
+
+    pub(crate) fn finish_frame(&mut self) {
+        self.blending_factor = self.accumulator.blending_factor();
+    }
+
+    pub fn set_updates_per_second(&mut self, new_updates_per_second: u32) {
+        self.accumulator.set_updates_per_second(new_updates_per_second);
+    }
+
+    pub fn set_update_rate(&mut self, new_updates_per_second: f64) {
+        self.accumulator.set_update_rate(new_updates_per_second);
+    }
+
+    pub fn set_time_step(&mut self, new_time_step: Duration) {
+        self.accumulator.set_time_step(new_time_step);
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn update_rate(&self) -> f64 {
+        self.accumulator.rate()
+    }
+
+    pub fn fixed_time_step(&self) -> f64 {
+        self.accumulator.step()
+    }
+
+    pub fn number_of_updates(&self) -> u64 {
+        self.number_of_updates
+    }
+
+    pub fn accumulated_time(&self) -> f64 {
+        self.accumulator.time()
+    }
+
+    pub fn blending_factor(&self) -> f64 {
+        self.blending_factor
+    }
+}
@@ -0,0 +1,90 @@
+use crate::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub(crate) type WallInstant = <Time as TimeTrait>::Instant;
+
+// Records frames in the Chrome Trace Event Format so that they can be loaded
+// into chrome://tracing or Perfetto. Timestamps are taken from the wall clock.
+pub struct ChromeTrace {
+    start: WallInstant,
+    events: Vec<TraceEvent>,
+}
+
+struct TraceEvent {
+    name: &'static str,
+    phase: char,
+    timestamp: f64,
+    duration: f64,
+    args: Vec<(&'static str, f64)>,
+}
+
+impl ChromeTrace {
+    pub fn new() -> Self {
+        Self { start: Time.now(), events: vec![] }
+    }
+
+    pub(crate) fn slice(&mut self, name: &'static str, start: &WallInstant, end: &WallInstant, args: &[(&'static str, f64)]) {
+        let timestamp = self.micros(start);
+        let duration = Time.sub(end, start) * 1e6;
+
+        self.events.push(TraceEvent { name, phase: 'X', timestamp, duration, args: args.to_vec() });
+    }
+
+    pub(crate) fn marker(&mut self, name: &'static str, at: &WallInstant, args: &[(&'static str, f64)]) {
+        let timestamp = self.micros(at);
+
+        self.events.push(TraceEvent { name, phase: 'i', timestamp, duration: 0.0, args: args.to_vec() });
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "{{\"displayTimeUnit\":\"ms\",\"traceEvents\":[")?;
+
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 { write!(writer, ",")?; }
+
+            write!(writer, "{{\"name\":\"{}\",\"cat\":\"game_loop\",\"ph\":\"{}\",\"ts\":{},\"pid\":1,\"tid\":1",
+                   event.name, event.phase, event.timestamp)?;
+
+            match event.phase {
+                'X' => write!(writer, ",\"dur\":{}", event.duration)?,
+                _ => write!(writer, ",\"s\":\"t\"")?,
+            }
+
+            write!(writer, ",\"args\":{{")?;
+
+            for (j, (key, value)) in event.args.iter().enumerate() {
+                if j > 0 { write!(writer, ",")?; }
+                write!(writer, "\"{}\":{}", key, value)?;
+            }
+
+            write!(writer, "}}}}")?;
+        }
+
+        write!(writer, "]}}")?;
+        writer.flush()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    fn micros(&self, instant: &WallInstant) -> f64 {
+        Time.sub(instant, &self.start) * 1e6
+    }
+}
+
+impl Default for ChromeTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::module_inception)]
+
 use crate::*;
 
 pub use helper::*;
@@ -54,9 +56,10 @@ mod helper {
 mod helper {
     use std::sync::Arc;
     use super::*;
-    use winit::event::{Event, WindowEvent};
+    use winit::event::{ElementState, Event, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
     use winit::event_loop::{ControlFlow, EventLoop};
     use winit::error::EventLoopError;
+    use winit::keyboard::{KeyCode, PhysicalKey};
     use winit::window::Window;
 
     pub use winit;
@@ -92,6 +95,207 @@ mod helper {
             }
         })
     }
+
+    // Key bindings for pausing and stepping through the game loop while
+    // debugging. Call handle from your window event handler to enable them.
+    pub struct StepKeys {
+        pub toggle_pause: KeyCode,
+        pub step_update: KeyCode,
+        pub step_frame: KeyCode,
+    }
+
+    impl Default for StepKeys {
+        fn default() -> Self {
+            Self { toggle_pause: KeyCode::F9, step_update: KeyCode::F10, step_frame: KeyCode::F11 }
+        }
+    }
+
+    impl StepKeys {
+        pub fn handle<G, T: TimeTrait, W, E>(&self, g: &mut GameLoop<G, T, W>, event: &Event<E>) -> bool {
+            let (code, repeat) = match event {
+                Event::WindowEvent { event: WindowEvent::KeyboardInput { event: KeyEvent {
+                    physical_key: PhysicalKey::Code(code), state: ElementState::Pressed, repeat, ..
+                }, .. }, .. } => (*code, *repeat),
+                _ => return false,
+            };
+
+            if code == self.toggle_pause && !repeat {
+                if g.is_paused() { g.resume(); } else { g.pause(); }
+            } else if code == self.step_update {
+                g.step_updates(1);
+            } else if code == self.step_frame {
+                g.step_frame();
+            } else {
+                return false;
+            }
+
+            true
+        }
+    }
+
+    // A recordable subset of the window events forwarded to your handler. Use
+    // InputEvent::from_event to convert them before recording and feeding them
+    // to your game so that replays see exactly the same inputs.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub enum InputEvent {
+        Key { code: KeyCode, pressed: bool, repeat: bool },
+        CursorMoved { x: f64, y: f64 },
+        MouseButton { button: MouseButton, pressed: bool },
+        MouseWheel { x: f64, y: f64, lines: bool },
+        Resized { width: u32, height: u32 },
+        Focused(bool),
+    }
+
+    impl InputEvent {
+        pub fn from_event<E>(event: &Event<E>) -> Option<Self> {
+            let event = match event {
+                Event::WindowEvent { event, .. } => event,
+                _ => return None,
+            };
+
+            Some(match event {
+                WindowEvent::KeyboardInput { event: KeyEvent { physical_key: PhysicalKey::Code(code), state, repeat, .. }, .. } if KEY_CODES.contains(code) => {
+                    InputEvent::Key { code: *code, pressed: *state == ElementState::Pressed, repeat: *repeat }
+                },
+                WindowEvent::CursorMoved { position, .. } => InputEvent::CursorMoved { x: position.x, y: position.y },
+                WindowEvent::MouseInput { state, button, .. } => {
+                    InputEvent::MouseButton { button: *button, pressed: *state == ElementState::Pressed }
+                },
+                WindowEvent::MouseWheel { delta: MouseScrollDelta::LineDelta(x, y), .. } => {
+                    InputEvent::MouseWheel { x: *x as f64, y: *y as f64, lines: true }
+                },
+                WindowEvent::MouseWheel { delta: MouseScrollDelta::PixelDelta(position), .. } => {
+                    InputEvent::MouseWheel { x: position.x, y: position.y, lines: false }
+                },
+                WindowEvent::Resized(size) => InputEvent::Resized { width: size.width, height: size.height },
+                WindowEvent::Focused(focused) => InputEvent::Focused(*focused),
+                _ => return None,
+            })
+        }
+    }
+
+    impl ReplayInput for InputEvent {
+        fn encode(&self, bytes: &mut Vec<u8>) {
+            match *self {
+                InputEvent::Key { code, pressed, repeat } => {
+                    bytes.push(0);
+                    key_code_index(code).encode(bytes);
+                    (pressed, repeat).encode(bytes);
+                },
+                InputEvent::CursorMoved { x, y } => {
+                    bytes.push(1);
+                    (x, y).encode(bytes);
+                },
+                InputEvent::MouseButton { button, pressed } => {
+                    bytes.push(2);
+                    encode_mouse_button(button, bytes);
+                    pressed.encode(bytes);
+                },
+                InputEvent::MouseWheel { x, y, lines } => {
+                    bytes.push(3);
+                    (x, y).encode(bytes);
+                    lines.encode(bytes);
+                },
+                InputEvent::Resized { width, height } => {
+                    bytes.push(4);
+                    (width, height).encode(bytes);
+                },
+                InputEvent::Focused(focused) => {
+                    bytes.push(5);
+                    focused.encode(bytes);
+                },
+            }
+        }
+
+        fn decode(bytes: &mut &[u8]) -> Option<Self> {
+            Some(match u8::decode(bytes)? {
+                0 => {
+                    let code = *KEY_CODES.get(u16::decode(bytes)? as usize)?;
+                    let (pressed, repeat) = <(bool, bool)>::decode(bytes)?;
+
+                    InputEvent::Key { code, pressed, repeat }
+                },
+                1 => {
+                    let (x, y) = <(f64, f64)>::decode(bytes)?;
+                    InputEvent::CursorMoved { x, y }
+                },
+                2 => {
+                    let button = decode_mouse_button(bytes)?;
+                    InputEvent::MouseButton { button, pressed: bool::decode(bytes)? }
+                },
+                3 => {
+                    let (x, y) = <(f64, f64)>::decode(bytes)?;
+                    InputEvent::MouseWheel { x, y, lines: bool::decode(bytes)? }
+                },
+                4 => {
+                    let (width, height) = <(u32, u32)>::decode(bytes)?;
+                    InputEvent::Resized { width, height }
+                },
+                5 => InputEvent::Focused(bool::decode(bytes)?),
+                _ => return None,
+            })
+        }
+    }
+
+    // KeyCode is non-exhaustive so keys are stored by their position in this
+    // table. New keys must be added to the end to keep old recordings valid.
+    const KEY_CODES: &[KeyCode] = {
+        use KeyCode::*;
+        &[
+            Backquote, Backslash, BracketLeft, BracketRight, Comma, Digit0, Digit1, Digit2, Digit3,
+            Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, Equal, IntlBackslash, IntlRo, IntlYen,
+            KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN,
+            KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ, Minus, Period,
+            Quote, Semicolon, Slash, AltLeft, AltRight, Backspace, CapsLock, ContextMenu,
+            ControlLeft, ControlRight, Enter, SuperLeft, SuperRight, ShiftLeft, ShiftRight, Space,
+            Tab, Convert, KanaMode, Lang1, Lang2, Lang3, Lang4, Lang5, NonConvert, Delete, End,
+            Help, Home, Insert, PageDown, PageUp, ArrowDown, ArrowLeft, ArrowRight, ArrowUp,
+            NumLock, Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7,
+            Numpad8, Numpad9, NumpadAdd, NumpadBackspace, NumpadClear, NumpadClearEntry,
+            NumpadComma, NumpadDecimal, NumpadDivide, NumpadEnter, NumpadEqual, NumpadHash,
+            NumpadMemoryAdd, NumpadMemoryClear, NumpadMemoryRecall, NumpadMemoryStore,
+            NumpadMemorySubtract, NumpadMultiply, NumpadParenLeft, NumpadParenRight, NumpadStar,
+            NumpadSubtract, Escape, Fn, FnLock, PrintScreen, ScrollLock, Pause, BrowserBack,
+            BrowserFavorites, BrowserForward, BrowserHome, BrowserRefresh, BrowserSearch,
+            BrowserStop, Eject, LaunchApp1, LaunchApp2, LaunchMail, MediaPlayPause, MediaSelect,
+            MediaStop, MediaTrackNext, MediaTrackPrevious, Power, Sleep, AudioVolumeDown,
+            AudioVolumeMute, AudioVolumeUp, WakeUp, Meta, Hyper, Turbo, Abort, Resume, Suspend,
+            Again, Copy, Cut, Find, Open, Paste, Props, Select, Undo, Hiragana, Katakana, F1, F2,
+            F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15, F16, F17, F18, F19, F20, F21,
+            F22, F23, F24, F25, F26, F27, F28, F29, F30, F31, F32, F33, F34, F35
+        ]
+    };
+
+    fn key_code_index(code: KeyCode) -> u16 {
+        KEY_CODES.iter().position(|c| *c == code).unwrap() as u16
+    }
+
+    // Other buttons store their number separately so that every u16 survives.
+    fn encode_mouse_button(button: MouseButton, bytes: &mut Vec<u8>) {
+        match button {
+            MouseButton::Left => bytes.push(0),
+            MouseButton::Right => bytes.push(1),
+            MouseButton::Middle => bytes.push(2),
+            MouseButton::Back => bytes.push(3),
+            MouseButton::Forward => bytes.push(4),
+            MouseButton::Other(n) => {
+                bytes.push(5);
+                n.encode(bytes);
+            },
+        }
+    }
+
+    fn decode_mouse_button(bytes: &mut &[u8]) -> Option<MouseButton> {
+        Some(match u8::decode(bytes)? {
+            0 => MouseButton::Left,
+            1 => MouseButton::Right,
+            2 => MouseButton::Middle,
+            3 => MouseButton::Back,
+            4 => MouseButton::Forward,
+            5 => MouseButton::Other(u16::decode(bytes)?),
+            _ => return None,
+        })
+    }
 }
 
 #[cfg(feature = "tao")]
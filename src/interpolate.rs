@@ -0,0 +1,128 @@
+use crate::*;
+
+// Interpolating renders the state between the last two updates which adds up
+// to one update of latency. Extrapolating predicts forward from the latest
+// state instead so that no latency is added, at the cost of mispredictions.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RenderTiming {
+    Interpolate,
+    Extrapolate,
+}
+
+// Blends between two values, e.g. linearly. Implement this for your own types
+// to customise how they're blended, e.g. spherically for rotations.
+pub trait Lerp {
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Lerp for f64 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t as f32
+    }
+}
+
+// Predicts where a value will be after moving at a velocity for some time.
+pub trait Extrapolate {
+    fn extrapolate(&self, velocity: &Self, seconds: f64) -> Self;
+}
+
+impl Extrapolate for f64 {
+    fn extrapolate(&self, velocity: &Self, seconds: f64) -> Self {
+        self + velocity * seconds
+    }
+}
+
+impl Extrapolate for f32 {
+    fn extrapolate(&self, velocity: &Self, seconds: f64) -> Self {
+        self + velocity * seconds as f32
+    }
+}
+
+impl<T: Lerp, const N: usize> Lerp for [T; N] {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| self[i].lerp(&other[i], t))
+    }
+}
+
+impl<T: Extrapolate, const N: usize> Extrapolate for [T; N] {
+    fn extrapolate(&self, velocity: &Self, seconds: f64) -> Self {
+        std::array::from_fn(|i| self[i].extrapolate(&velocity[i], seconds))
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($name:ident $index:tt),*) => {
+        impl<$($name: Lerp),*> Lerp for ($($name,)*) {
+            fn lerp(&self, other: &Self, t: f64) -> Self {
+                ($(self.$index.lerp(&other.$index, t),)*)
+            }
+        }
+
+        impl<$($name: Extrapolate),*> Extrapolate for ($($name,)*) {
+            fn extrapolate(&self, velocity: &Self, seconds: f64) -> Self {
+                ($(self.$index.extrapolate(&velocity.$index, seconds),)*)
+            }
+        }
+    };
+}
+
+tuple_impls!(A 0);
+tuple_impls!(A 0, B 1);
+tuple_impls!(A 0, B 1, C 2);
+tuple_impls!(A 0, B 1, C 2, D 3);
+
+// Keeps the values from the last two fixed updates so that render can blend
+// between them. Call set once per update and get from your render function.
+#[derive(Clone, Debug)]
+pub struct Interpolated<T> {
+    previous: T,
+    current: T,
+}
+
+impl<T: Lerp + Clone> Interpolated<T> {
+    pub fn new(value: T) -> Self {
+        Self { previous: value.clone(), current: value }
+    }
+
+    pub fn set(&mut self, value: T) {
+        self.previous = std::mem::replace(&mut self.current, value);
+    }
+
+    // Moves straight to the value without blending from the previous one,
+    // e.g. when teleporting.
+    pub fn reset(&mut self, value: T) {
+        self.previous = value.clone();
+        self.current = value;
+    }
+
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    pub fn blend(&self, blending_factor: f64) -> T {
+        self.previous.lerp(&self.current, blending_factor.clamp(0.0, 1.0))
+    }
+
+    // Continues past the current value at the rate it changed on the last
+    // update, i.e. by blending beyond it.
+    pub fn extrapolate(&self, blending_factor: f64) -> T {
+        self.previous.lerp(&self.current, 1.0 + blending_factor.clamp(0.0, 1.0))
+    }
+
+    pub fn get<G, C: TimeTrait, W>(&self, g: &GameLoop<G, C, W>) -> T {
+        match g.render_timing {
+            RenderTiming::Interpolate => self.blend(g.blending_factor()),
+            RenderTiming::Extrapolate => self.extrapolate(g.blending_factor()),
+        }
+    }
+}
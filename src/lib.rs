@@ -1,7 +1,44 @@
+mod accounting;
+#[cfg(feature = "tokio")]
+mod async_loop;
 mod base;
+mod catch_up;
+mod channel;
+mod chrome_trace;
 mod helper;
+mod interpolate;
+mod lockstep;
+mod loop_mode;
+mod pacing;
+mod replay;
+mod rollback;
+mod scheduler;
+mod stats;
+#[cfg(not(target_arch = "wasm32"))]
+mod threaded;
 mod time;
+mod trace;
+mod transport;
+mod triple_buffer;
 
+pub use accounting::*;
+#[cfg(feature = "tokio")]
+pub use async_loop::*;
 pub use base::*;
+pub use catch_up::*;
+pub use channel::*;
+pub use chrome_trace::*;
 pub use helper::*;
+pub use interpolate::*;
+pub use lockstep::*;
+pub use loop_mode::*;
+pub use pacing::*;
+pub use replay::*;
+pub use rollback::*;
+pub use scheduler::*;
+pub use stats::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use threaded::*;
 pub use time::*;
+pub use transport::*;
+pub use triple_buffer::*;
@@ -0,0 +1,126 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+// A game that advances in lockstep with the other peers. Advance is called
+// once per tick with every player's input and must be deterministic.
+pub trait LockstepGame {
+    type Input: Clone + Default;
+
+    fn advance(&mut self, inputs: &[Self::Input]);
+}
+
+// Runs a LockstepGame on each fixed update. Local inputs are scheduled
+// input_delay ticks in the future to give them time to reach the other peers.
+// If any player's input for the current tick hasn't arrived, the simulation
+// stalls but the game loop carries on rendering.
+pub struct Lockstep<G: LockstepGame, T> {
+    game: G,
+    transport: T,
+    players: usize,
+    local_player: usize,
+    input_delay: u64,
+    current_tick: u64,
+    next_local_tick: u64,
+    inputs: BTreeMap<u64, Vec<Option<G::Input>>>,
+    stalled: bool,
+    stalls: u64,
+}
+
+impl<G: LockstepGame, T: Transport<G::Input>> Lockstep<G, T> {
+    pub fn new(game: G, players: usize, local_player: usize, input_delay: u64, transport: T) -> Self {
+        Self {
+            game,
+            transport,
+            players,
+            local_player,
+            input_delay,
+            current_tick: 0,
+            next_local_tick: input_delay,
+            inputs: BTreeMap::new(),
+            stalled: false,
+            stalls: 0,
+        }
+    }
+
+    // Call this from your update function. The local input is only asked for
+    // once per tick so that inputs aren't lost while the simulation is stalled.
+    // Returns false if the simulation stalled.
+    pub fn tick<F: FnOnce() -> G::Input>(&mut self, local_input: F) -> bool {
+        // Messages come from the network so drop any for unknown players, any
+        // that claim to be from this one and any for the ticks before the
+        // first delayed input since nobody should be sending those.
+        for InputMessage { player, tick, input } in self.transport.receive() {
+            let valid = player < self.players && player != self.local_player;
+            if valid && tick >= self.current_tick.max(self.input_delay) { self.store(player, tick, input); }
+        }
+
+        let local_tick = self.current_tick + self.input_delay;
+
+        if self.next_local_tick == local_tick {
+            let input = local_input();
+
+            self.transport.send(InputMessage { player: self.local_player, tick: local_tick, input: input.clone() });
+            self.store(self.local_player, local_tick, input);
+            self.next_local_tick += 1;
+        }
+
+        self.stalled = !self.has_inputs(self.current_tick);
+
+        if self.stalled {
+            self.stalls += 1;
+            return false;
+        }
+
+        let inputs = self.take_inputs(self.current_tick);
+
+        self.game.advance(&inputs);
+        self.current_tick += 1;
+
+        true
+    }
+
+    fn store(&mut self, player: usize, tick: u64, input: G::Input) {
+        let players = self.players;
+        self.inputs.entry(tick).or_insert_with(|| vec![None; players])[player] = Some(input);
+    }
+
+    // Nobody sends inputs for the ticks before the first delayed input.
+    fn has_inputs(&self, tick: u64) -> bool {
+        tick < self.input_delay || self.inputs.get(&tick).is_some_and(|inputs| inputs.iter().all(Option::is_some))
+    }
+
+    fn take_inputs(&mut self, tick: u64) -> Vec<G::Input> {
+        match self.inputs.remove(&tick) {
+            Some(inputs) => inputs.into_iter().map(Option::unwrap_or_default).collect(),
+            None => vec![G::Input::default(); self.players],
+        }
+    }
+
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    pub fn local_player(&self) -> usize {
+        self.local_player
+    }
+
+    pub fn input_delay(&self) -> u64 {
+        self.input_delay
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    pub fn stalls(&self) -> u64 {
+        self.stalls
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoopMode {
+    Fixed,
+    Variable,
+    SemiFixed,
+}
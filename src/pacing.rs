@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PacingMode {
+    Sleep,
+    Hybrid { spin_threshold: f64 },
+}
+
+// Sleeps for most of the remaining frame and spin-waits for the rest. The
+// threshold grows to cover the worst recent oversleep, which decays over time.
+#[derive(Copy, Clone, Default)]
+pub(crate) struct Pacer {
+    oversleep: f64,
+}
+
+impl Pacer {
+    pub fn wait<T: TimeTrait>(&mut self, mode: PacingMode, clock: &mut T, seconds: f64) {
+        if mode == PacingMode::Sleep {
+            clock.sleep(seconds);
+            return;
+        }
+
+        let spin_threshold = self.spin_threshold(mode);
+        let start = clock.now();
+        let sleep_time = seconds - spin_threshold;
+
+        if sleep_time > 0.0 {
+            clock.sleep(sleep_time);
+
+            let slept = clock.sub(&clock.now(), &start);
+            self.oversleep = (slept - sleep_time).max(self.oversleep * 0.99);
+        }
+
+        let remaining = seconds - clock.sub(&clock.now(), &start);
+        if remaining > 0.0 { clock.spin(remaining); }
+    }
+
+    pub fn spin_threshold(&self, mode: PacingMode) -> f64 {
+        match mode {
+            PacingMode::Sleep => 0.0,
+            PacingMode::Hybrid { spin_threshold } => spin_threshold.max(self.oversleep),
+        }
+    }
+}
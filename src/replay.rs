@@ -0,0 +1,328 @@
+use crate::*;
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+const MAGIC: &[u8] = b"GLIR\x01";
+
+// Inputs are encoded to a compact byte format so that sessions can be saved
+// to a file and replayed later. Decode should consume exactly what encode wrote.
+pub trait ReplayInput: Sized {
+    fn encode(&self, bytes: &mut Vec<u8>);
+    fn decode(bytes: &mut &[u8]) -> Option<Self>;
+}
+
+// Records the inputs fed to each update. Inputs should be recorded against
+// the update that will consume them, i.e. g.number_of_updates() from within
+// an update, or from a window event handler before the next update runs.
+pub struct InputRecorder<I> {
+    groups: Vec<(u64, u64, Vec<u8>)>,
+    number_of_updates: u64,
+    marker: PhantomData<I>,
+}
+
+impl<I: ReplayInput> InputRecorder<I> {
+    pub fn new() -> Self {
+        Self { groups: vec![], number_of_updates: 0, marker: PhantomData }
+    }
+
+    pub fn record(&mut self, update: u64, input: &I) -> io::Result<()> {
+        match self.groups.last_mut() {
+            Some((last, _, _)) if *last > update => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "inputs must be recorded in update order"));
+            },
+            Some((last, count, bytes)) if *last == update => {
+                *count += 1;
+                input.encode(bytes);
+            },
+            _ => {
+                let mut bytes = vec![];
+                input.encode(&mut bytes);
+
+                self.groups.push((update, 1, bytes));
+            },
+        }
+
+        self.number_of_updates = self.number_of_updates.max(update + 1);
+        Ok(())
+    }
+
+    // Records how long the session ran for so that replays can continue past
+    // the last input, e.g. to reproduce a bug that happened some time later.
+    pub fn finish(&mut self, number_of_updates: u64) {
+        self.number_of_updates = self.number_of_updates.max(number_of_updates);
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|(_, count, _)| *count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    pub fn number_of_updates(&self) -> u64 {
+        self.number_of_updates
+    }
+
+    // The format is a header followed by groups of inputs. Each group stores
+    // the number of updates since the previous group, how many inputs there
+    // are and then the inputs themselves.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut bytes = MAGIC.to_vec();
+        write_varint(&mut bytes, self.number_of_updates);
+
+        let mut previous = 0;
+
+        for (update, count, inputs) in &self.groups {
+            write_varint(&mut bytes, update - previous);
+            write_varint(&mut bytes, *count);
+            bytes.extend_from_slice(inputs);
+
+            previous = *update;
+        }
+
+        writer.write_all(&bytes)?;
+        writer.flush()
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<I: ReplayInput> Default for InputRecorder<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct InputPlayer<I> {
+    groups: VecDeque<(u64, Vec<I>)>,
+    number_of_updates: u64,
+}
+
+impl<I: ReplayInput> InputPlayer<I> {
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+
+        Self::decode(&bytes).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid input recording"))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    fn decode(mut bytes: &[u8]) -> Option<Self> {
+        if !bytes.starts_with(MAGIC) { return None; }
+        bytes = &bytes[MAGIC.len()..];
+
+        let number_of_updates = read_varint(&mut bytes)?;
+        let mut groups = VecDeque::new();
+        let mut update = 0;
+
+        while !bytes.is_empty() {
+            update += read_varint(&mut bytes)?;
+            let count = read_varint(&mut bytes)?;
+
+            let inputs = (0..count).map(|_| I::decode(&mut bytes)).collect::<Option<Vec<_>>>()?;
+            groups.push_back((update, inputs));
+        }
+
+        Some(Self { groups, number_of_updates })
+    }
+
+    // Returns the inputs that were recorded for the given update. Inputs for
+    // earlier updates that weren't asked for are skipped.
+    pub fn inputs_for(&mut self, update: u64) -> Vec<I> {
+        while let Some((next, _)) = self.groups.front() {
+            if *next > update { break; }
+
+            let (next, inputs) = self.groups.pop_front().unwrap();
+            if next == update { return inputs; }
+        }
+
+        vec![]
+    }
+
+    pub fn number_of_updates(&self) -> u64 {
+        self.number_of_updates
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+// Runs a recorded session as fast as possible. The game loop should be set up
+// the same way as when the session was recorded, e.g. with the same update
+// rate and time accounting. The clock advances by one update per frame so each
+// update receives the inputs that were recorded for it. Nothing is rendered.
+pub fn replay<G, I, U>(mut g: GameLoop<G, ManualClock, ()>, mut player: InputPlayer<I>, mut update: U) -> GameLoop<G, ManualClock, ()>
+    where I: ReplayInput,
+          U: FnMut(&mut GameLoop<G, ManualClock, ()>, Vec<I>),
+{
+    g.catch_up_policy = CatchUpPolicy::KeepRemainder { max_updates: 1 };
+    g.max_frame_time = g.max_frame_time.max(g.fixed_time_step());
+
+    while g.number_of_updates() < player.number_of_updates() {
+        g.clock.advance(g.fixed_time_step());
+
+        let running = g.next_frame(|g| {
+            let inputs = player.inputs_for(g.number_of_updates());
+            update(g, inputs);
+        }, |_| {});
+
+        if !running { break; }
+    }
+
+    g
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut value = 0;
+
+    for shift in (0..64).step_by(7) {
+        let (byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 { return Some(value); }
+    }
+
+    None
+}
+
+fn read_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if bytes.len() < len { return None; }
+
+    let (head, rest) = bytes.split_at(len);
+    *bytes = rest;
+
+    Some(head)
+}
+
+macro_rules! unsigned_input {
+    ($($t:ty),*) => {$(
+        impl ReplayInput for $t {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                write_varint(bytes, *self as u64);
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                read_varint(bytes)?.try_into().ok()
+            }
+        }
+    )*};
+}
+
+// Signed integers are zigzag encoded so that small negative numbers are small.
+macro_rules! signed_input {
+    ($($t:ty),*) => {$(
+        impl ReplayInput for $t {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                let value = *self as i64;
+                write_varint(bytes, ((value << 1) ^ (value >> 63)) as u64);
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                let value = read_varint(bytes)?;
+                let value = (value >> 1) as i64 ^ -((value & 1) as i64);
+
+                value.try_into().ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! float_input {
+    ($($t:ty),*) => {$(
+        impl ReplayInput for $t {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                let bytes = read_bytes(bytes, std::mem::size_of::<$t>())?;
+                Some(<$t>::from_le_bytes(bytes.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+unsigned_input!(u8, u16, u32, u64, usize);
+signed_input!(i8, i16, i32, i64, isize);
+float_input!(f32, f64);
+
+impl ReplayInput for bool {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self as u8);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        match u8::decode(bytes)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl ReplayInput for String {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        write_varint(bytes, self.len() as u64);
+        bytes.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let len = read_varint(bytes)? as usize;
+        String::from_utf8(read_bytes(bytes, len)?.to_vec()).ok()
+    }
+}
+
+impl<T: ReplayInput> ReplayInput for Vec<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        write_varint(bytes, self.len() as u64);
+        for item in self { item.encode(bytes); }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let len = read_varint(bytes)?;
+        (0..len).map(|_| T::decode(bytes)).collect()
+    }
+}
+
+impl<T: ReplayInput> ReplayInput for Option<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.is_some().encode(bytes);
+        if let Some(value) = self { value.encode(bytes); }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        if bool::decode(bytes)? { T::decode(bytes).map(Some) } else { Some(None) }
+    }
+}
+
+impl<A: ReplayInput, B: ReplayInput> ReplayInput for (A, B) {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.0.encode(bytes);
+        self.1.encode(bytes);
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        Some((A::decode(bytes)?, B::decode(bytes)?))
+    }
+}
@@ -0,0 +1,187 @@
+use crate::*;
+use std::collections::{BTreeMap, VecDeque};
+
+// A game that can be rolled back. Advance is called once per tick with every
+// player's input and must be deterministic for rollback to stay in sync.
+pub trait RollbackGame {
+    type Input: Clone + PartialEq + Default;
+    type Snapshot;
+
+    fn advance(&mut self, inputs: &[Self::Input]);
+    fn save(&self) -> Self::Snapshot;
+    fn load(&mut self, snapshot: &Self::Snapshot);
+}
+
+struct Frame<S, I> {
+    tick: u64,
+    snapshot: S,
+    inputs: Vec<I>,
+}
+
+// Runs a RollbackGame on each fixed update. Remote inputs that haven't arrived
+// yet are predicted by repeating the player's last known input. When a late
+// input differs from the prediction, the game is rolled back to that tick and
+// re-simulated to the present, all within the current update.
+pub struct Rollback<G: RollbackGame, T> {
+    game: G,
+    transport: T,
+    local_player: usize,
+    max_prediction: u64,
+    current_tick: u64,
+    inputs: Vec<BTreeMap<u64, G::Input>>,
+    next_unconfirmed: Vec<u64>,
+    frames: VecDeque<Frame<G::Snapshot, G::Input>>,
+    rollbacks: u64,
+    resimulated_ticks: u64,
+}
+
+impl<G: RollbackGame, T: Transport<G::Input>> Rollback<G, T> {
+    pub fn new(game: G, players: usize, local_player: usize, transport: T) -> Self {
+        Self {
+            game,
+            transport,
+            local_player,
+            max_prediction: 8,
+            current_tick: 0,
+            inputs: vec![BTreeMap::new(); players],
+            next_unconfirmed: vec![0; players],
+            frames: VecDeque::new(),
+            rollbacks: 0,
+            resimulated_ticks: 0,
+        }
+    }
+
+    // Call this from your update function. Returns false if the game stalled
+    // because it's too far ahead of the inputs that have been confirmed.
+    pub fn tick(&mut self, local_input: G::Input) -> bool {
+        let rollback_to = self.receive();
+
+        if let Some(tick) = rollback_to { self.resimulate(tick); }
+        if self.current_tick.saturating_sub(self.confirmed_tick()) >= self.max_prediction { return false; }
+
+        let tick = self.current_tick;
+        self.transport.send(InputMessage { player: self.local_player, tick, input: local_input.clone() });
+        self.confirm(self.local_player, tick, local_input);
+
+        let inputs = self.inputs_for(tick);
+        let snapshot = self.game.save();
+
+        self.game.advance(&inputs);
+        self.frames.push_back(Frame { tick, snapshot, inputs });
+        self.current_tick += 1;
+
+        self.discard_confirmed_frames();
+        true
+    }
+
+    // Returns the earliest tick that was simulated with a misprediction.
+    fn receive(&mut self) -> Option<u64> {
+        let mut rollback_to: Option<u64> = None;
+
+        for InputMessage { player, tick, input } in self.transport.receive() {
+            // Messages come from the network so drop any for unknown players
+            // or that claim to be from this one.
+            if player >= self.inputs.len() || player == self.local_player { continue; }
+            if tick < self.next_unconfirmed[player] || self.inputs[player].contains_key(&tick) { continue; }
+
+            if let Some(frame) = self.frame(tick) {
+                if frame.inputs[player] != input {
+                    rollback_to = Some(rollback_to.map_or(tick, |t| t.min(tick)));
+                }
+            }
+
+            self.confirm(player, tick, input);
+        }
+
+        rollback_to
+    }
+
+    fn resimulate(&mut self, tick: u64) {
+        let start = (tick - self.frames[0].tick) as usize;
+        self.game.load(&self.frames[start].snapshot);
+
+        for i in start..self.frames.len() {
+            let inputs = self.inputs_for(self.frames[i].tick);
+
+            if i > start { self.frames[i].snapshot = self.game.save(); }
+            self.game.advance(&inputs);
+            self.frames[i].inputs = inputs;
+        }
+
+        self.rollbacks += 1;
+        self.resimulated_ticks += (self.frames.len() - start) as u64;
+    }
+
+    fn confirm(&mut self, player: usize, tick: u64, input: G::Input) {
+        let inputs = &mut self.inputs[player];
+        inputs.insert(tick, input);
+
+        let next = &mut self.next_unconfirmed[player];
+        while inputs.contains_key(next) { *next += 1; }
+    }
+
+    // Uses the confirmed input if there is one, otherwise predicts that the
+    // player is still doing whatever they were last known to be doing.
+    fn inputs_for(&self, tick: u64) -> Vec<G::Input> {
+        self.inputs.iter().map(|inputs| {
+            inputs.range(..=tick).next_back().map(|(_, input)| input.clone()).unwrap_or_default()
+        }).collect()
+    }
+
+    // Frames before the confirmed tick were simulated with the correct inputs
+    // so they can never be rolled back to.
+    fn discard_confirmed_frames(&mut self) {
+        let confirmed = self.confirmed_tick();
+
+        while self.frames.front().is_some_and(|frame| frame.tick < confirmed) {
+            self.frames.pop_front();
+        }
+
+        // Keep the latest confirmed input of each player for predictions.
+        for inputs in self.inputs.iter_mut() {
+            *inputs = inputs.split_off(&confirmed.saturating_sub(1));
+        }
+    }
+
+    fn frame(&self, tick: u64) -> Option<&Frame<G::Snapshot, G::Input>> {
+        let first = self.frames.front()?.tick;
+        self.frames.get(tick.checked_sub(first)? as usize)
+    }
+
+    pub fn set_max_prediction(&mut self, ticks: u64) {
+        self.max_prediction = ticks.max(1);
+    }
+
+    pub fn max_prediction(&self) -> u64 {
+        self.max_prediction
+    }
+
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    pub fn transport(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    pub fn local_player(&self) -> usize {
+        self.local_player
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.current_tick
+    }
+
+    // The first tick for which some player's input hasn't arrived yet.
+    pub fn confirmed_tick(&self) -> u64 {
+        self.next_unconfirmed.iter().copied().min().unwrap_or(self.current_tick)
+    }
+
+    pub fn rollbacks(&self) -> u64 {
+        self.rollbacks
+    }
+
+    pub fn resimulated_ticks(&self) -> u64 {
+        self.resimulated_ticks
+    }
+}
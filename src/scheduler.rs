@@ -0,0 +1,108 @@
+use crate::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+enum Trigger {
+    At { game_nanos: u64 },
+    Every { game_nanos: u64, interval: u64 },
+    EveryUpdates { update: u64, interval: u64 },
+}
+
+pub(crate) struct Timer<G, T: TimeTrait, W> {
+    id: TimerId,
+    trigger: Trigger,
+    pub callback: Callback<G, T, W>,
+}
+
+pub(crate) struct Scheduler<G, T: TimeTrait, W> {
+    timers: Vec<Timer<G, T, W>>,
+    rescheduled: Vec<Timer<G, T, W>>,
+    firing: Option<TimerId>,
+    next_id: u64,
+}
+
+impl<G, T: TimeTrait, W> Scheduler<G, T, W> {
+    pub fn new() -> Self {
+        Self { timers: vec![], rescheduled: vec![], firing: None, next_id: 0 }
+    }
+
+    pub fn at(&mut self, game_nanos: u64, callback: Callback<G, T, W>) -> TimerId {
+        self.add(Trigger::At { game_nanos }, callback)
+    }
+
+    pub fn every(&mut self, game_nanos: u64, interval: u64, callback: Callback<G, T, W>) -> TimerId {
+        self.add(Trigger::Every { game_nanos: game_nanos + interval.max(1), interval: interval.max(1) }, callback)
+    }
+
+    pub fn every_updates(&mut self, update: u64, interval: u64, callback: Callback<G, T, W>) -> TimerId {
+        self.add(Trigger::EveryUpdates { update: update + interval.max(1), interval: interval.max(1) }, callback)
+    }
+
+    fn add(&mut self, trigger: Trigger, callback: Callback<G, T, W>) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+
+        self.timers.push(Timer { id, trigger, callback });
+        id
+    }
+
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        if self.firing == Some(id) {
+            self.firing = None;
+            return true;
+        }
+
+        let before = self.timers.len() + self.rescheduled.len();
+
+        self.timers.retain(|t| t.id != id);
+        self.rescheduled.retain(|t| t.id != id);
+
+        before != self.timers.len() + self.rescheduled.len()
+    }
+
+    pub fn is_scheduled(&self, id: TimerId) -> bool {
+        self.firing == Some(id) || self.timers.iter().chain(&self.rescheduled).any(|t| t.id == id)
+    }
+
+    // Timers based on game time fire before those based on the number of
+    // updates. Within each, they fire in the order they were due, then by id.
+    pub fn take_due(&mut self, game_nanos: u64, updates: u64) -> Option<Timer<G, T, W>> {
+        let index = self.timers.iter().enumerate()
+            .filter_map(|(index, timer)| match timer.trigger {
+                Trigger::At { game_nanos: due } |
+                Trigger::Every { game_nanos: due, .. } if due <= game_nanos => Some((0, due, timer.id.0, index)),
+                Trigger::EveryUpdates { update: due, .. } if due <= updates => Some((1, due, timer.id.0, index)),
+                _ => None,
+            })
+            .min()
+            .map(|(_, _, _, index)| index)?;
+
+        let timer = self.timers.remove(index);
+        self.firing = Some(timer.id);
+
+        Some(timer)
+    }
+
+    // Repeating timers fire at most once per update so they are set aside
+    // until the update has finished firing timers.
+    pub fn finish(&mut self, mut timer: Timer<G, T, W>, game_nanos: u64, updates: u64) {
+        if self.firing.take() != Some(timer.id) { return; }
+
+        match &mut timer.trigger {
+            Trigger::At { .. } => return,
+            Trigger::Every { game_nanos: due, interval } => {
+                *due = game_nanos + *interval - (game_nanos - *due) % *interval;
+            },
+            Trigger::EveryUpdates { update: due, interval } => {
+                *due = updates + *interval - (updates - *due) % *interval;
+            },
+        }
+
+        self.rescheduled.push(timer);
+    }
+
+    pub fn finish_update(&mut self) {
+        self.timers.append(&mut self.rescheduled);
+    }
+}
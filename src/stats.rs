@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+// A rolling window of recent frames so that FPS counters don't need to keep
+// their own ring buffers.
+pub struct FrameStats {
+    window: usize,
+    frames: VecDeque<Frame>,
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct Frame {
+    pub frame_time: f64,
+    pub updates: u64,
+    pub rendered: bool,
+    pub update_time: f64,
+    pub render_time: f64,
+    pub sleep_time: f64,
+}
+
+impl FrameStats {
+    pub fn new(window: usize) -> Self {
+        Self { window, frames: VecDeque::new() }
+    }
+
+    pub(crate) fn record(&mut self, frame: Frame) {
+        if self.window == 0 { return; }
+        if self.frames.len() == self.window { self.frames.pop_front(); }
+
+        self.frames.push_back(frame);
+    }
+
+    pub fn set_window(&mut self, window: usize) {
+        while self.frames.len() > window { self.frames.pop_front(); }
+
+        self.window = window;
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn total_time(&self) -> f64 {
+        self.frames.iter().map(|f| f.frame_time).sum()
+    }
+
+    pub fn fps(&self) -> f64 {
+        let renders = self.frames.iter().filter(|f| f.rendered).count();
+
+        per_second(renders as f64, self.total_time())
+    }
+
+    pub fn ups(&self) -> f64 {
+        let updates = self.frames.iter().map(|f| f.updates).sum::<u64>();
+
+        per_second(updates as f64, self.total_time())
+    }
+
+    pub fn mean_frame_time(&self) -> f64 {
+        self.mean_of(|f| f.frame_time)
+    }
+
+    pub fn mean_update_time(&self) -> f64 {
+        self.mean_of(|f| f.update_time)
+    }
+
+    pub fn mean_render_time(&self) -> f64 {
+        self.mean_of(|f| f.render_time)
+    }
+
+    pub fn mean_sleep_time(&self) -> f64 {
+        self.mean_of(|f| f.sleep_time)
+    }
+
+    pub fn min_frame_time(&self) -> f64 {
+        self.frame_time_percentile(0.0)
+    }
+
+    pub fn max_frame_time(&self) -> f64 {
+        self.frame_time_percentile(100.0)
+    }
+
+    pub fn p50_frame_time(&self) -> f64 {
+        self.frame_time_percentile(50.0)
+    }
+
+    pub fn p95_frame_time(&self) -> f64 {
+        self.frame_time_percentile(95.0)
+    }
+
+    pub fn p99_frame_time(&self) -> f64 {
+        self.frame_time_percentile(99.0)
+    }
+
+    pub fn frame_time_percentile(&self, percentile: f64) -> f64 {
+        percentile_of(self.frames.iter().map(|f| f.frame_time), percentile)
+    }
+
+    fn mean_of<F: Fn(&Frame) -> f64>(&self, value: F) -> f64 {
+        if self.frames.is_empty() { return 0.0; }
+
+        self.frames.iter().map(value).sum::<f64>() / self.frames.len() as f64
+    }
+}
+
+fn per_second(count: f64, seconds: f64) -> f64 {
+    if seconds > 0.0 { count / seconds } else { 0.0 }
+}
+
+// Uses the nearest-rank method, i.e. the smallest value that is greater than
+// or equal to the given percentage of values.
+pub(crate) fn percentile_of<I: Iterator<Item = f64>>(values: I, percentile: f64) -> f64 {
+    let mut values = values.collect::<Vec<_>>();
+    if values.is_empty() { return 0.0; }
+
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * values.len() as f64).ceil() as usize;
+    values[rank.max(1) - 1]
+}
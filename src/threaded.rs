@@ -0,0 +1,121 @@
+use crate::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+struct Published<S> {
+    state: S,
+    timestamp: Instant,
+    updates: u64,
+    step: f64,
+}
+
+// Runs the fixed updates on a dedicated thread so that a heavy simulation
+// doesn't block rendering. After each frame with updates, the simulation
+// publishes a snapshot of the game which the render thread picks up through
+// a triple buffer. Call next_frame from the thread that renders, e.g. from
+// your winit event loop.
+pub struct ThreadedGameLoop<G, S> {
+    reader: TripleBufferReader<Published<S>>,
+    previous: Published<S>,
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<G>>,
+    number_of_renders: u64,
+}
+
+impl<G: Send + 'static, S: Clone + Send + 'static> ThreadedGameLoop<G, S> {
+    pub fn new<U, P>(game: G, updates_per_second: u32, max_frame_time: f64, mut update: U, snapshot: P) -> Self
+        where U: FnMut(&mut GameLoop<G, Time, ()>) + Send + 'static,
+              P: Fn(&G) -> S + Send + 'static,
+    {
+        let initial = Published { state: snapshot(&game), timestamp: Instant::now(), updates: 0, step: 1.0 / updates_per_second as f64 };
+        let (mut writer, reader) = triple_buffer(initial.clone());
+        let running = Arc::new(AtomicBool::new(true));
+        let still_running = running.clone();
+
+        let thread = std::thread::spawn(move || {
+            let mut g = GameLoop::new(game, updates_per_second, max_frame_time, ());
+            let mut published_updates = 0;
+
+            // Nothing is rendered here so wait between frames rather than spin.
+            g.max_renders_per_second = Some(updates_per_second);
+
+            while still_running.load(Ordering::Relaxed) && g.next_frame(&mut update, |g| {
+                if g.number_of_updates() == published_updates { return; }
+
+                // The state is from the most recent update rather than now.
+                let since_update = Duration::from_secs_f64(g.accumulated_time().max(0.0));
+                let timestamp = Instant::now().checked_sub(since_update).unwrap_or_else(Instant::now);
+
+                published_updates = g.number_of_updates();
+                *writer.write() = Published { state: snapshot(&g.game), timestamp, updates: published_updates, step: g.fixed_time_step() };
+                writer.publish();
+            }) {}
+
+            g.game
+        });
+
+        Self { reader, previous: initial, running, thread: Some(thread), number_of_renders: 0 }
+    }
+
+    // Calls render with the two most recent snapshots and how far to blend
+    // between them. Returns false once the simulation has stopped.
+    pub fn next_frame<R>(&mut self, mut render: R) -> bool
+        where R: FnMut(&S, &S, f64),
+    {
+        if !self.is_running() { return false; }
+
+        // Keep the old snapshot before its slot is handed back to the writer.
+        if self.reader.has_update() {
+            self.previous = self.reader.read().clone();
+            self.reader.update();
+        }
+
+        render(&self.previous.state, &self.reader.read().state, self.blending_factor());
+        self.number_of_renders += 1;
+
+        true
+    }
+
+    // The render thread may miss snapshots so blend over however many updates
+    // apart the two snapshots are rather than the time between publishing them.
+    pub fn blending_factor(&self) -> f64 {
+        let current = self.reader.read();
+        let interval = current.updates.saturating_sub(self.previous.updates) as f64 * current.step;
+
+        if interval <= 0.0 { return 1.0; }
+
+        (Instant::now().saturating_duration_since(current.timestamp).as_secs_f64() / interval).min(1.0)
+    }
+
+    // The thread finishes without clearing the flag if the update panics.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Relaxed) && !self.thread.as_ref().is_some_and(JoinHandle::is_finished)
+    }
+
+    pub fn number_of_renders(&self) -> u64 {
+        self.number_of_renders
+    }
+
+    pub fn exit(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+
+    // Stops the simulation and returns the game once its thread has finished.
+    pub fn join(mut self) -> G {
+        self.exit();
+
+        match self.thread.take().unwrap().join() {
+            Ok(game) => game,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl<G, S> Drop for ThreadedGameLoop<G, S> {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
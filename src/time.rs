@@ -1,8 +1,26 @@
-pub trait TimeTrait : Copy {
-    fn now() -> Self;
-    fn sub(&self, other: &Self) -> f64;
-    fn supports_sleep() -> bool;
-    fn sleep(seconds: f64);
+#![allow(clippy::module_inception)]
+
+use std::time::Duration;
+
+pub trait TimeTrait {
+    type Instant: Copy;
+
+    fn now(&self) -> Self::Instant;
+    fn sub(&self, later: &Self::Instant, earlier: &Self::Instant) -> f64;
+    fn supports_sleep(&self) -> bool;
+    fn sleep(&mut self, seconds: f64);
+
+    fn sub_nanos(&self, later: &Self::Instant, earlier: &Self::Instant) -> u64 {
+        crate::nanos(self.sub(later, earlier))
+    }
+
+    fn spin(&mut self, seconds: f64) {
+        let start = self.now();
+
+        while self.sub(&self.now(), &start) < seconds {
+            std::hint::spin_loop();
+        }
+    }
 }
 
 pub use time::*;
@@ -10,26 +28,32 @@ pub use time::*;
 #[cfg(not(target_arch = "wasm32"))]
 mod time {
     use super::*;
-    use std::time::{Instant, Duration};
+    use std::time::Instant;
     use std::thread::sleep;
 
-    #[derive(Copy, Clone)]
-    pub struct Time(Instant);
+    #[derive(Copy, Clone, Default)]
+    pub struct Time;
 
     impl TimeTrait for Time {
-        fn now() -> Self {
-            Self(Instant::now())
+        type Instant = Instant;
+
+        fn now(&self) -> Instant {
+            Instant::now()
+        }
+
+        fn sub(&self, later: &Instant, earlier: &Instant) -> f64 {
+            later.duration_since(*earlier).as_secs_f64()
         }
 
-        fn sub(&self, other: &Self) -> f64 {
-            self.0.duration_since(other.0).as_secs_f64()
+        fn sub_nanos(&self, later: &Instant, earlier: &Instant) -> u64 {
+            later.duration_since(*earlier).as_nanos() as u64
         }
 
-        fn supports_sleep() -> bool {
+        fn supports_sleep(&self) -> bool {
             true
         }
 
-        fn sleep(seconds: f64) {
+        fn sleep(&mut self, seconds: f64) {
             sleep(Duration::from_secs_f64(seconds));
         }
     }
@@ -40,24 +64,70 @@ mod time {
     use super::*;
     use web_sys::window;
 
-    #[derive(Copy, Clone)]
-    pub struct Time(f64);
+    #[derive(Copy, Clone, Default)]
+    pub struct Time;
 
     impl TimeTrait for Time {
-        fn now() -> Self {
-            Self(window().unwrap().performance().unwrap().now() / 1000.)
+        type Instant = f64;
+
+        fn now(&self) -> f64 {
+            window().unwrap().performance().unwrap().now() / 1000.
         }
 
-        fn sub(&self, other: &Self) -> f64 {
-            self.0 - other.0
+        fn sub(&self, later: &f64, earlier: &f64) -> f64 {
+            later - earlier
         }
 
-        fn supports_sleep() -> bool {
+        fn supports_sleep(&self) -> bool {
             false
         }
 
-        fn sleep(_seconds: f64) {
+        fn sleep(&mut self, _seconds: f64) {
             unimplemented!("Not supported for WASM.");
         }
     }
 }
+
+#[derive(Copy, Clone, Default)]
+pub struct ManualClock {
+    elapsed: Duration,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&mut self, seconds: f64) {
+        self.elapsed += Duration::from_secs_f64(seconds);
+    }
+}
+
+impl TimeTrait for ManualClock {
+    type Instant = Duration;
+
+    fn now(&self) -> Self::Instant {
+        self.elapsed
+    }
+
+    fn sub(&self, later: &Self::Instant, earlier: &Self::Instant) -> f64 {
+        later.saturating_sub(*earlier).as_secs_f64()
+    }
+
+    fn sub_nanos(&self, later: &Self::Instant, earlier: &Self::Instant) -> u64 {
+        later.saturating_sub(*earlier).as_nanos() as u64
+    }
+
+    // Sleeping moves the clock forward instantly so that tests don't block.
+    fn supports_sleep(&self) -> bool {
+        true
+    }
+
+    fn sleep(&mut self, seconds: f64) {
+        self.advance(seconds);
+    }
+
+    fn spin(&mut self, seconds: f64) {
+        self.advance(seconds);
+    }
+}
@@ -0,0 +1,43 @@
+// These expand to nothing unless the tracing feature is enabled so that the
+// game loop doesn't pay for spans when nothing is subscribed to them.
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($arg:tt)*) => { tracing::trace_span!($($arg)*).entered() };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($arg:tt)*) => { () };
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => { tracing::$level!($($arg)*) };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {};
+}
+
+// Entered spans can't be held across an await so futures are instrumented
+// with the span instead. The span is built first as it may borrow from
+// whatever the future borrows mutably.
+#[cfg(all(feature = "tokio", feature = "tracing"))]
+macro_rules! trace_instrument {
+    ($future:expr, $($arg:tt)*) => {{
+        let span = tracing::trace_span!($($arg)*);
+        tracing::Instrument::instrument($future, span)
+    }};
+}
+
+#[cfg(all(feature = "tokio", not(feature = "tracing")))]
+macro_rules! trace_instrument {
+    ($future:expr, $($arg:tt)*) => { $future };
+}
+
+pub(crate) use trace_event;
+#[cfg(feature = "tokio")]
+pub(crate) use trace_instrument;
+pub(crate) use trace_span;
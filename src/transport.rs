@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InputMessage<I> {
+    pub player: usize,
+    pub tick: u64,
+    pub input: I,
+}
+
+// Sends inputs to the other peers. Receive is called once per tick and should
+// return every message that has arrived since it was last called.
+pub trait Transport<I> {
+    fn send(&mut self, message: InputMessage<I>);
+    fn receive(&mut self) -> Vec<InputMessage<I>>;
+}
+
+// An in-process transport for testing. Messages are delivered after the
+// receiving end has called receive latency times, i.e. after that many ticks.
+pub struct MemoryTransport<I> {
+    outgoing: Rc<RefCell<Mailbox<I>>>,
+    incoming: Rc<RefCell<Mailbox<I>>>,
+    latency: u64,
+}
+
+struct Mailbox<I> {
+    polls: u64,
+    messages: VecDeque<(u64, InputMessage<I>)>,
+}
+
+impl<I> MemoryTransport<I> {
+    pub fn pair(latency: u64) -> (Self, Self) {
+        let a = Rc::new(RefCell::new(Mailbox { polls: 0, messages: VecDeque::new() }));
+        let b = Rc::new(RefCell::new(Mailbox { polls: 0, messages: VecDeque::new() }));
+
+        (Self { outgoing: a.clone(), incoming: b.clone(), latency },
+         Self { outgoing: b, incoming: a, latency })
+    }
+
+    pub fn set_latency(&mut self, latency: u64) {
+        self.latency = latency;
+    }
+
+    pub fn latency(&self) -> u64 {
+        self.latency
+    }
+}
+
+impl<I> Transport<I> for MemoryTransport<I> {
+    fn send(&mut self, message: InputMessage<I>) {
+        let mut mailbox = self.outgoing.borrow_mut();
+        let deliver_at = mailbox.polls + self.latency;
+
+        mailbox.messages.push_back((deliver_at, message));
+    }
+
+    fn receive(&mut self) -> Vec<InputMessage<I>> {
+        let mut mailbox = self.incoming.borrow_mut();
+        mailbox.polls += 1;
+
+        let polls = mailbox.polls;
+        let mut messages = vec![];
+
+        while mailbox.messages.front().is_some_and(|(deliver_at, _)| *deliver_at < polls) {
+            messages.push(mailbox.messages.pop_front().unwrap().1);
+        }
+
+        messages
+    }
+}
@@ -0,0 +1,70 @@
+use std::cell::UnsafeCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// The back slot's index lives in the low bits. The flag is set when the writer
+// has published into it and the reader hasn't swapped it out yet.
+const INDEX: u8 = 0b011;
+const FRESH: u8 = 0b100;
+
+struct Shared<T> {
+    slots: [UnsafeCell<T>; 3],
+    back: AtomicU8,
+}
+
+// Each slot is owned by exactly one of the writer, the reader or the back
+// buffer at a time and ownership only changes hands through the atomic swap.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+pub struct TripleBufferWriter<T> {
+    shared: Arc<Shared<T>>,
+    index: u8,
+}
+
+pub struct TripleBufferReader<T> {
+    shared: Arc<Shared<T>>,
+    index: u8,
+}
+
+// A lock-free way to hand the latest value from one thread to another. The
+// writer never waits for the reader and the reader always gets the most
+// recently published value, skipping any it didn't get round to.
+pub fn triple_buffer<T: Clone>(value: T) -> (TripleBufferWriter<T>, TripleBufferReader<T>) {
+    let slots = [UnsafeCell::new(value.clone()), UnsafeCell::new(value.clone()), UnsafeCell::new(value)];
+    let shared = Arc::new(Shared { slots, back: AtomicU8::new(2) });
+
+    (TripleBufferWriter { shared: shared.clone(), index: 0 }, TripleBufferReader { shared, index: 1 })
+}
+
+impl<T> TripleBufferWriter<T> {
+    // The slot holds an older value so overwrite all of it before publishing.
+    pub fn write(&mut self) -> &mut T {
+        unsafe { &mut *self.shared.slots[self.index as usize].get() }
+    }
+
+    pub fn publish(&mut self) {
+        let back = self.shared.back.swap(self.index | FRESH, Ordering::AcqRel);
+        self.index = back & INDEX;
+    }
+}
+
+impl<T> TripleBufferReader<T> {
+    // Only the reader clears the flag so this stays true until update is called.
+    pub fn has_update(&self) -> bool {
+        self.shared.back.load(Ordering::Relaxed) & FRESH != 0
+    }
+
+    // Swaps in the latest published value. Returns false if there wasn't one.
+    pub fn update(&mut self) -> bool {
+        if !self.has_update() { return false; }
+
+        let back = self.shared.back.swap(self.index, Ordering::AcqRel);
+        self.index = back & INDEX;
+
+        true
+    }
+
+    pub fn read(&self) -> &T {
+        unsafe { &*self.shared.slots[self.index as usize].get() }
+    }
+}
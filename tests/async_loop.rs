@@ -0,0 +1,112 @@
+#![cfg(feature = "tokio")]
+
+use game_loop::{game_loop_async, GameLoop, TokioTime};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration, Instant};
+
+#[tokio::test(start_paused = true)]
+async fn it_sleeps_until_each_fixed_update_is_due() {
+    let start = Instant::now();
+
+    let g = game_loop_async(0, 10, 1.0, async |g| {
+        g.game += 1;
+        if g.game == 10 { g.exit(); }
+    }, |_| {}).await;
+
+    let elapsed = start.elapsed().as_secs_f64();
+
+    assert_eq!(g.game, 10);
+    assert_eq!(g.number_of_updates(), 10);
+    assert!((0.99..1.01).contains(&elapsed), "{}", elapsed);
+    assert!(g.number_of_renders() <= 11);
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_lets_update_await_messages_from_other_tasks() {
+    let (sender, mut receiver) = mpsc::channel(8);
+
+    tokio::spawn(async move {
+        for message in 0..3 {
+            sleep(Duration::from_millis(250)).await;
+            sender.send(message).await.unwrap();
+        }
+    });
+
+    let g = game_loop_async(vec![], 60, 1.0, async |g| {
+        match receiver.recv().await {
+            Some(message) => g.game.push(message),
+            None => g.exit(),
+        }
+    }, |_| {}).await;
+
+    assert_eq!(g.game, [0, 1, 2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_renders_after_the_frames_updates_have_been_awaited() {
+    let mut g = GameLoop::with_clock(0, 10, 1.0, (), TokioTime);
+    let mut rendered = vec![];
+
+    sleep(Duration::from_millis(250)).await;
+
+    g.next_frame_async(async |g| {
+        sleep(Duration::from_millis(1)).await;
+        g.game += 1;
+    }, |g| rendered.push(g.game)).await;
+
+    assert_eq!(rendered, [2]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_awaits_each_update_in_place() {
+    let mut g = GameLoop::with_clock(vec![], 10, 1.0, (), TokioTime);
+    let mut rendered = vec![];
+
+    g.schedule_every_updates(1, |g| { let n = g.number_of_updates(); g.game.push(("timer", n)); });
+    sleep(Duration::from_millis(350)).await;
+
+    g.next_frame_async(async |g| {
+        sleep(Duration::from_millis(1)).await;
+        let n = g.number_of_updates();
+        g.game.push(("update", n));
+    }, |g| rendered.push(g.number_of_updates())).await;
+
+    assert_eq!(g.game, [("update", 0), ("timer", 1), ("update", 1), ("timer", 2), ("update", 2), ("timer", 3)]);
+    assert_eq!(rendered, [3]);
+    assert_eq!(g.number_of_renders(), 1);
+    assert!((g.stats().ups() * g.stats().total_time() - 3.0).abs() < 1e-9);
+    assert!((g.blending_factor() - 0.5).abs() < 1e-6, "{}", g.blending_factor());
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_sleeps_for_the_maximum_frame_time_when_the_update_rate_is_zero() {
+    let mut g = GameLoop::with_clock(0, 0, 0.5, (), TokioTime);
+    let start = Instant::now();
+
+    assert!(g.next_frame_async(async |g| g.game += 1, |_| {}).await);
+
+    assert_eq!(g.game, 0);
+    assert_eq!(start.elapsed(), Duration::from_millis(500));
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_runs_the_game_loop_in_a_spawned_task() {
+    let (sender, mut receiver) = mpsc::channel(8);
+
+    let task = tokio::spawn(game_loop_async(vec![], 10, 1.0, async move |g| {
+        match receiver.recv().await {
+            Some(message) => g.game.push(message),
+            None => g.exit(),
+        }
+    }, |_| {}));
+
+    for message in 0..3 {
+        sender.send(message).await.unwrap();
+    }
+
+    drop(sender);
+
+    let g = task.await.unwrap();
+
+    assert_eq!(g.game, [0, 1, 2]);
+}
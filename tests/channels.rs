@@ -0,0 +1,131 @@
+use game_loop::{GameLoop, ManualClock, TimeAccounting};
+
+type Log = Vec<&'static str>;
+
+fn new_game_loop() -> GameLoop<Log, ManualClock, ()> {
+    let mut g = GameLoop::with_clock(vec![], 8, 1.0, (), ManualClock::new());
+    g.set_time_accounting(TimeAccounting::Integer);
+    g
+}
+
+#[test]
+fn it_calls_each_channel_according_to_its_own_updates_per_second() {
+    let mut g = new_game_loop();
+
+    g.add_channel("physics", 8, |g| g.game.push("physics"));
+    g.add_channel("ai", 2, |g| g.game.push("ai"));
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.channel("physics").unwrap().number_of_updates(), 8);
+    assert_eq!(g.channel("ai").unwrap().number_of_updates(), 2);
+    assert_eq!(g.number_of_updates(), 8);
+}
+
+#[test]
+fn it_runs_channel_updates_in_the_order_they_were_due() {
+    let mut g = new_game_loop();
+
+    g.add_channel("ai", 10, |g| g.game.push("ai"));
+    g.add_channel("network", 30, |g| g.game.push("network"));
+
+    g.clock.advance(0.2);
+    g.next_frame(|_| {}, |_| {});
+
+    // Updates that are due at exactly the same time run in the order the
+    // channels were added.
+    assert_eq!(g.game, vec![
+        "network", "network", "ai", "network",
+        "network", "network", "ai", "network",
+    ]);
+}
+
+#[test]
+fn it_provides_a_blending_factor_for_each_channel() {
+    let mut g = new_game_loop();
+
+    g.add_channel("physics", 8, |_| {});
+    g.add_channel("ai", 2, |_| {});
+
+    g.clock.advance(0.3125);
+    g.next_frame(|_| {}, |_| {});
+
+    let physics = g.channel("physics").unwrap();
+    let ai = g.channel("ai").unwrap();
+
+    assert_eq!(physics.blending_factor(), 0.5);
+    assert_eq!(physics.fixed_time_step(), 0.125);
+    assert_eq!(ai.blending_factor(), 0.625);
+    assert_eq!(ai.accumulated_time(), 0.3125);
+}
+
+#[test]
+fn it_provides_the_channel_time_step_as_the_delta_time() {
+    let mut g = new_game_loop();
+
+    g.add_channel("ai", 2, |g| assert_eq!(g.delta_time(), 0.5));
+
+    g.clock.advance(1.0);
+    g.next_frame(|g| assert_eq!(g.delta_time(), 0.125), |_| {});
+
+    assert_eq!(g.channel("ai").unwrap().number_of_updates(), 2);
+}
+
+#[test]
+fn it_does_not_call_channels_while_paused() {
+    let mut g = new_game_loop();
+
+    g.add_channel("physics", 8, |_| panic!("channel called while paused"));
+    g.pause();
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.channel("physics").unwrap().accumulated_time(), 0.0);
+}
+
+#[test]
+fn it_can_change_and_remove_channels() {
+    let mut g = new_game_loop();
+
+    g.add_channel("physics", 8, |g| g.game.push("physics"));
+    g.add_channel("ai", 2, |g| {
+        g.game.push("ai");
+        g.remove_channel("ai");
+    });
+
+    g.channel_mut("physics").unwrap().set_updates_per_second(4);
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.game.iter().filter(|name| **name == "physics").count(), 4);
+    assert_eq!(g.game.iter().filter(|name| **name == "ai").count(), 1);
+    assert!(g.channel("ai").is_none());
+}
+
+#[test]
+fn it_runs_channels_that_are_due_together_in_the_order_they_were_added_in_float_mode() {
+    let mut g = GameLoop::with_clock(vec![], 8, 1.0, (), ManualClock::new());
+
+    g.add_channel("ai", 10, |g| g.game.push("ai"));
+    g.add_channel("physics", 30, |g| g.game.push("physics"));
+
+    for _ in 0..50 {
+        g.clock.advance(0.05);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    let (mut ai, mut physics) = (0, 0);
+
+    // Every third physics update is due at the same time as an ai update.
+    for name in g.game {
+        if name == "ai" { ai += 1; continue; }
+
+        physics += 1;
+        if physics % 3 == 0 { assert_eq!(ai, physics / 3); }
+    }
+
+    assert_eq!((ai, physics), (25, 75));
+}
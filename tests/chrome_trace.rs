@@ -0,0 +1,89 @@
+use game_loop::{game_loop, ChromeTrace, GameLoop, ManualClock};
+use std::fs;
+
+const GAME: &str = "fake game";
+
+#[test]
+fn it_records_update_and_render_slices_for_each_frame() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.start_chrome_trace();
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    let json = to_json(&g.stop_chrome_trace().unwrap());
+
+    assert!(json.starts_with("{\"displayTimeUnit\":\"ms\",\"traceEvents\":["));
+    assert!(json.ends_with("]}"));
+
+    assert_eq!(count(&json, "\"name\":\"frame\""), 1);
+    assert_eq!(count(&json, "\"name\":\"update\""), 2);
+    assert_eq!(count(&json, "\"name\":\"render\""), 1);
+    assert_eq!(count(&json, "\"name\":\"sleep\""), 0);
+
+    assert!(json.contains("\"args\":{\"update\":1,\"delta_time\":0.125}"));
+}
+
+#[test]
+fn it_records_sleeps_and_marks_frames_that_were_clamped() {
+    let mut g = GameLoop::with_clock(GAME, 8, 0.5, (), ManualClock::new());
+    g.max_renders_per_second = Some(10);
+    g.start_chrome_trace();
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    g.window_occluded = true;
+    g.next_frame(|_| {}, |_| {});
+
+    let json = to_json(g.chrome_trace().unwrap());
+
+    assert_eq!(count(&json, "\"name\":\"clamped_frame\",\"cat\":\"game_loop\",\"ph\":\"i\""), 1);
+    assert!(json.contains("\"args\":{\"frame_time\":1,\"max_frame_time\":0.5}"));
+
+    assert_eq!(count(&json, "\"name\":\"render\""), 1);
+    assert_eq!(count(&json, "\"name\":\"sleep\""), 2);
+}
+
+#[test]
+fn it_does_not_record_anything_once_the_trace_is_stopped() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.start_chrome_trace();
+    let trace = g.stop_chrome_trace().unwrap();
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    assert!(trace.is_empty());
+    assert!(g.chrome_trace().is_none());
+}
+
+#[test]
+fn it_can_save_a_trace_from_the_headless_game_loop() {
+    let mut g = game_loop(GAME, 100, 1.0, |g| {
+        if g.number_of_updates() == 5 { g.exit(); }
+    }, |g| {
+        if g.chrome_trace().is_none() { g.start_chrome_trace(); }
+    });
+
+    let path = std::env::temp_dir().join("game_loop_chrome_trace.json");
+    g.stop_chrome_trace().unwrap().save(&path).unwrap();
+
+    let json = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(count(&json, "\"name\":\"update\"") > 0);
+    assert!(count(&json, "\"name\":\"render\"") > 0);
+}
+
+fn to_json(trace: &ChromeTrace) -> String {
+    let mut bytes = vec![];
+    trace.write_to(&mut bytes).unwrap();
+
+    String::from_utf8(bytes).unwrap()
+}
+
+fn count(json: &str, pattern: &str) -> usize {
+    json.matches(pattern).count()
+}
@@ -1,4 +1,7 @@
-use game_loop::game_loop;
+#![allow(clippy::redundant_static_lifetimes, clippy::bool_assert_comparison)]
+
+use game_loop::{game_loop, GameLoop, ManualClock, PacingMode, TimeAccounting, TimeTrait};
+use std::cell::Cell;
 use std::thread::sleep;
 use std::time::Duration;
 
@@ -26,6 +29,7 @@ fn it_provides_game_to_the_closures() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn it_provides_updates_per_second_to_the_closures() {
     game_loop(GAME, 100, 1.0, |g| {
         assert_eq!(g.updates_per_second, 100);
@@ -124,6 +128,29 @@ fn it_calls_the_render_function_as_quickly_as_possible() {
     assert!(control.number_of_renders() > 1000);
 }
 
+#[test]
+fn it_sleeps_between_renders_when_the_renders_per_second_are_capped() {
+    let control = game_loop(GAME, 100, 1.0, |g| {
+        if g.running_time() > 0.1 { g.exit(); }
+    }, |g| {
+        g.max_renders_per_second = Some(100);
+    });
+
+    assert!(control.number_of_renders() < 20);
+}
+
+#[test]
+fn it_sleeps_and_spins_between_renders_when_using_hybrid_pacing() {
+    let control = game_loop(GAME, 100, 1.0, |g| {
+        if g.running_time() > 0.1 { g.exit(); }
+    }, |g| {
+        g.max_renders_per_second = Some(100);
+        g.pacing = PacingMode::Hybrid { spin_threshold: 0.001 };
+    });
+
+    assert!(control.number_of_renders() < 20);
+}
+
 #[test]
 fn it_limits_the_maximum_frame_time_which_reduces_the_number_of_updates() {
     let mut i = 0;
@@ -164,6 +191,349 @@ fn it_can_re_measure_how_much_time_has_accumulated_mid_way_through_a_render() {
     });
 }
 
+#[test]
+fn it_queries_the_clock_instance_that_the_game_loop_owns() {
+    let clock = TickingClock { now: Cell::new(10.0), tick: 0.25 };
+    let mut g = GameLoop::with_clock(GAME, 4, 1.0, (), clock);
+
+    for _ in 0..3 {
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    assert_eq!(g.number_of_updates(), 3);
+    assert_eq!(g.running_time(), 0.75);
+    assert_eq!(g.current_instant(), 10.75);
+}
+
+#[test]
+fn it_calls_update_an_exact_number_of_times_when_the_clock_is_advanced_manually() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 4);
+    assert_eq!(g.blending_factor(), 0.0);
+
+    g.clock.advance(0.1875);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 5);
+    assert_eq!(g.blending_factor(), 0.5);
+    assert_eq!(g.running_time(), 0.6875);
+}
+
+#[test]
+fn it_does_not_call_update_if_the_manual_clock_has_not_moved() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.next_frame(|_| {}, |_| {});
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 0);
+    assert_eq!(g.number_of_renders(), 2);
+}
+
+#[test]
+fn it_advances_the_manual_clock_when_the_game_loop_sleeps() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.window_occluded = true;
+
+    g.next_frame(|_| {}, |_| {});
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 1);
+    assert_eq!(g.number_of_renders(), 0);
+}
+
+#[test]
+fn it_does_not_call_update_or_accumulate_time_while_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.pause();
+    assert!(g.is_paused());
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| panic!("update called while paused"), |_| {});
+
+    assert_eq!(g.number_of_updates(), 0);
+    assert_eq!(g.number_of_renders(), 1);
+    assert_eq!(g.accumulated_time(), 0.0);
+
+    g.resume();
+    assert!(!g.is_paused());
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+}
+
+#[test]
+fn it_excludes_paused_periods_from_game_time_but_not_running_time() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |g| g.pause());
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |g| g.resume());
+
+    g.clock.advance(0.125);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.running_time(), 0.875);
+    assert_eq!(g.game_time(), 0.375);
+}
+
+#[test]
+fn it_stops_calling_update_as_soon_as_the_game_is_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.5);
+    g.next_frame(|g| g.pause(), |_| {});
+
+    assert_eq!(g.number_of_updates(), 1);
+}
+
+#[test]
+fn it_scales_the_time_that_accumulates_without_changing_the_fixed_time_step() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.time_scale = 0.25;
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.fixed_time_step(), 0.125);
+    assert_eq!(g.game_time(), 0.25);
+    assert_eq!(g.running_time(), 1.0);
+
+    g.time_scale = 4.0;
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 18);
+    assert_eq!(g.game_time(), 2.25);
+}
+
+#[test]
+fn it_clamps_the_real_elapsed_time_before_scaling_it() {
+    let mut g = GameLoop::with_clock(GAME, 8, 0.5, (), ManualClock::new());
+
+    g.time_scale = 2.0;
+    g.clock.advance(2.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.last_frame_time(), 0.5);
+    assert_eq!(g.game_time(), 1.0);
+    assert_eq!(g.number_of_updates(), 8);
+}
+
+#[test]
+fn it_runs_an_exact_number_of_updates_when_stepping_while_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.pause();
+    g.step_updates(3);
+
+    g.clock.advance(0.0625);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 3);
+    assert_eq!(g.accumulated_time(), 0.0);
+    assert_eq!(g.game_time(), 0.375);
+    assert!(g.is_paused());
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 3);
+}
+
+#[test]
+fn it_advances_by_one_frame_when_stepping_a_frame_while_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.pause();
+    g.step_frame();
+
+    g.clock.advance(0.3125);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.blending_factor(), 0.5);
+    assert!(g.is_paused());
+
+    g.clock.advance(0.3125);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.game_time(), 0.3125);
+}
+
+#[test]
+fn it_ignores_steps_that_are_requested_while_not_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.step_updates(3);
+    g.next_frame(|_| {}, |_| {});
+    g.pause();
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 0);
+}
+
+#[test]
+fn it_counts_updates_exactly_when_using_integer_time_accounting() {
+    let mut g = GameLoop::with_clock(GAME, 60, 1.0, (), ManualClock::new());
+    g.set_time_accounting(TimeAccounting::Integer);
+
+    for _ in 0..3600 {
+        g.clock.advance(1.0);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    assert_eq!(g.number_of_updates(), 216_000);
+    assert_eq!(g.accumulated_time(), 0.0);
+    assert_eq!(g.blending_factor(), 0.0);
+    assert_eq!(g.running_time(), 3600.0);
+}
+
+#[test]
+fn it_derives_accumulated_time_and_blending_factor_from_integer_time_accounting() {
+    let mut g = GameLoop::with_clock(GAME, 3, 2.0, (), ManualClock::new());
+    g.set_time_accounting(TimeAccounting::Integer);
+
+    g.clock.advance(1.5);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.time_accounting(), TimeAccounting::Integer);
+    assert_eq!(g.number_of_updates(), 4);
+    assert_eq!(g.accumulated_time(), 1.0 / 6.0);
+    assert_eq!(g.blending_factor(), 0.5);
+}
+
+#[test]
+fn it_keeps_the_accumulated_time_when_switching_time_accounting() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.0625);
+    g.next_frame(|_| {}, |_| {});
+
+    g.set_time_accounting(TimeAccounting::Integer);
+    assert_eq!(g.accumulated_time(), 0.0625);
+
+    g.set_time_accounting(TimeAccounting::Float);
+    assert_eq!(g.accumulated_time(), 0.0625);
+}
+
+#[test]
+fn it_does_not_update_while_the_update_rate_is_zero() {
+    for accounting in [TimeAccounting::Float, TimeAccounting::Integer] {
+        let mut g = GameLoop::with_clock(GAME, 0, 1.0, (), ManualClock::new());
+        g.set_time_accounting(accounting);
+        g.add_channel("ai", 0, |_| {});
+
+        g.clock.advance(0.5);
+        g.next_frame(|_| {}, |_| {});
+
+        assert_eq!(g.number_of_updates(), 0);
+        assert_eq!(g.accumulated_time(), 0.5);
+        assert!(g.blending_factor() < 1e-9);
+
+        g.set_updates_per_second(4);
+        g.channel_mut("ai").unwrap().set_updates_per_second(4);
+        g.next_frame(|_| {}, |_| {});
+
+        assert_eq!(g.number_of_updates(), 2);
+        assert_eq!(g.channel("ai").unwrap().number_of_updates(), 2);
+
+        g.set_update_rate(0.0);
+        g.clock.advance(1.0);
+        g.next_frame(|_| {}, |_| {});
+
+        assert_eq!(g.number_of_updates(), 2);
+    }
+}
+
+#[test]
+#[should_panic(expected = "the time step must be greater than zero")]
+fn it_does_not_allow_a_time_step_of_zero() {
+    let mut g = GameLoop::with_clock(GAME, 60, 1.0, (), ManualClock::new());
+    g.set_time_step(Duration::ZERO);
+}
+
+#[test]
+#[allow(deprecated)]
+fn it_supports_fractional_update_rates() {
+    let mut g = GameLoop::with_clock(GAME, 60, 200.0, (), ManualClock::new());
+    g.set_time_accounting(TimeAccounting::Integer);
+    g.set_update_rate(59.94);
+
+    assert_eq!(g.update_rate(), 59.94);
+    assert_eq!(g.updates_per_second, 60);
+    assert_eq!(g.fixed_time_step(), 1.0 / 59.94);
+
+    g.clock.advance(100.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 5994);
+    assert_eq!(g.blending_factor(), 0.0);
+}
+
+#[test]
+#[allow(deprecated)]
+fn it_supports_update_rates_given_as_a_duration_between_updates() {
+    let mut g = GameLoop::with_clock(GAME, 60, 10.0, (), ManualClock::new());
+    g.set_time_step(Duration::from_millis(1500));
+
+    assert_eq!(g.fixed_time_step(), 1.5);
+    assert_eq!(g.update_rate(), 1.0 / 1.5);
+    assert_eq!(g.updates_per_second, 1);
+
+    g.clock.advance(3.75);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.blending_factor(), 0.5);
+}
+
+#[test]
+fn it_can_construct_a_game_loop_with_a_fractional_or_duration_update_rate() {
+    let g: GameLoop<_, ManualClock, _> = GameLoop::with_update_rate(GAME, 0.5, 1.0, ());
+    assert_eq!(g.fixed_time_step(), 2.0);
+
+    let g: GameLoop<_, ManualClock, _> = GameLoop::with_time_step(GAME, Duration::from_millis(250), 1.0, ());
+    assert_eq!(g.update_rate(), 4.0);
+}
+
+// A clock that moves forward by a fixed amount each time it is queried.
+struct TickingClock {
+    now: Cell<f64>,
+    tick: f64,
+}
+
+impl TimeTrait for TickingClock {
+    type Instant = f64;
+
+    fn now(&self) -> f64 {
+        let now = self.now.get();
+        self.now.set(now + self.tick);
+        now
+    }
+
+    fn sub(&self, later: &f64, earlier: &f64) -> f64 {
+        later - earlier
+    }
+
+    fn supports_sleep(&self) -> bool {
+        false
+    }
+
+    fn sleep(&mut self, _seconds: f64) {}
+}
+
 fn approx_eq(actual: f64, expected: f64) {
     let delta = (actual - expected).abs();
 
@@ -0,0 +1,114 @@
+use game_loop::{Extrapolate, GameLoop, Interpolated, Lerp, ManualClock, RenderTiming};
+use std::f64::consts::PI;
+
+#[test]
+fn it_blends_between_the_values_from_the_last_two_updates() {
+    let mut g = GameLoop::with_clock(Interpolated::new(0.0), 4, 1.0, (), ManualClock::new());
+    let mut rendered = vec![];
+
+    for _ in 0..3 {
+        g.clock.advance(0.375);
+        g.next_frame(|g| {
+            let position = g.game.current() + 10.0;
+            g.game.set(position);
+        }, |g| rendered.push(g.game.get(g)));
+    }
+
+    assert_eq!(g.number_of_updates(), 4);
+    assert_eq!(rendered, [5.0, 20.0, 35.0]);
+}
+
+#[test]
+fn it_does_not_blend_after_a_reset() {
+    let mut value = Interpolated::new(1.0_f32);
+
+    value.set(2.0);
+    assert_eq!(value.blend(0.5), 1.5);
+
+    value.reset(10.0);
+    assert_eq!(value.blend(0.5), 10.0);
+    assert_eq!(value.previous(), &10.0);
+}
+
+#[test]
+fn it_clamps_the_blending_factor_so_that_it_does_not_extrapolate() {
+    let mut value = Interpolated::new(0.0);
+    value.set(1.0);
+
+    assert_eq!(value.blend(-1.0), 0.0);
+    assert_eq!(value.blend(2.5), 1.0);
+}
+
+#[test]
+fn it_blends_tuples_and_arrays_element_wise() {
+    let mut value = Interpolated::new(([0.0, 10.0], (1.0_f32, -4.0)));
+    value.set(([4.0, 20.0], (3.0, 4.0)));
+
+    assert_eq!(value.blend(0.25), ([1.0, 12.5], (1.5, -2.0)));
+}
+
+// Angles take the shortest way round rather than blending linearly.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Angle(f64);
+
+impl Lerp for Angle {
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        let delta = (other.0 - self.0 + PI).rem_euclid(2.0 * PI) - PI;
+        Angle(self.0 + delta * t)
+    }
+}
+
+#[test]
+fn it_supports_custom_blending_through_the_lerp_trait() {
+    let mut angle = Interpolated::new(Angle(0.9 * PI));
+    angle.set(Angle(-0.9 * PI));
+
+    let halfway = angle.blend(0.5).0;
+
+    assert!((halfway - PI).abs() < 1e-9);
+}
+
+#[test]
+fn it_reports_the_time_since_the_last_update() {
+    let mut g = GameLoop::with_clock((), 4, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.375);
+    g.next_frame(|_| {}, |g| assert_eq!(g.extrapolation_time(), 0.125));
+}
+
+#[test]
+fn it_extrapolates_from_the_latest_value_when_chosen() {
+    let mut g = GameLoop::with_clock(Interpolated::new(0.0), 4, 1.0, (), ManualClock::new());
+    g.render_timing = RenderTiming::Extrapolate;
+    let mut rendered = vec![];
+
+    for _ in 0..3 {
+        g.clock.advance(0.375);
+        g.next_frame(|g| {
+            let position = g.game.current() + 10.0;
+            g.game.set(position);
+        }, |g| rendered.push(g.game.get(g)));
+    }
+
+    assert_eq!(rendered, [15.0, 30.0, 45.0]);
+}
+
+#[test]
+fn it_predicts_values_from_their_velocity_at_the_rendered_time() {
+    let mut g = GameLoop::with_clock(100.0, 4, 1.0, (), ManualClock::new());
+    g.clock.advance(0.375);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.predict(&g.game, &8.0), 99.0);
+
+    g.render_timing = RenderTiming::Extrapolate;
+    assert_eq!(g.predict(&g.game, &8.0), 101.0);
+}
+
+#[test]
+fn it_extrapolates_tuples_and_arrays_element_wise() {
+    let value = ([1.0, 2.0], (3.0_f32, 4.0));
+    let velocity = ([2.0, -2.0], (1.0, 0.0));
+
+    assert_eq!(value.extrapolate(&velocity, 0.5), ([2.0, 1.0], (3.5, 4.0)));
+}
@@ -0,0 +1,133 @@
+use game_loop::{GameLoop, InputMessage, Lockstep, LockstepGame, ManualClock, MemoryTransport, Transport};
+use std::cell::Cell;
+
+#[derive(Default)]
+struct Units {
+    positions: [i64; 2],
+    history: Vec<[i64; 2]>,
+}
+
+impl LockstepGame for Units {
+    type Input = i64;
+
+    fn advance(&mut self, inputs: &[i64]) {
+        for (position, input) in self.positions.iter_mut().zip(inputs) {
+            *position += input;
+        }
+
+        self.history.push(self.positions);
+    }
+}
+
+fn input(player: usize, tick: u64) -> i64 {
+    ((tick / 3 + player as u64) % 3) as i64 - 1
+}
+
+type Peer = GameLoop<Lockstep<Units, MemoryTransport<i64>>, ManualClock, ()>;
+
+fn peers(latency: u64, input_delay: u64) -> (Peer, Peer) {
+    let (a, b) = MemoryTransport::pair(latency);
+
+    let a = Lockstep::new(Units::default(), 2, 0, input_delay, a);
+    let b = Lockstep::new(Units::default(), 2, 1, input_delay, b);
+
+    (GameLoop::with_clock(a, 60, 1.0, (), ManualClock::new()),
+     GameLoop::with_clock(b, 60, 1.0, (), ManualClock::new()))
+}
+
+fn next_frame(g: &mut Peer) {
+    g.clock.advance(1.0 / 60.0);
+
+    g.next_frame(|g| {
+        let player = g.game.local_player();
+        let tick = g.game.current_tick() + g.game.input_delay();
+
+        g.game.tick(|| input(player, tick));
+    }, |_| {});
+}
+
+#[test]
+fn it_simulates_the_same_ticks_on_every_peer() {
+    let (mut a, mut b) = peers(2, 4);
+
+    for _ in 0..100 {
+        next_frame(&mut a);
+        next_frame(&mut b);
+    }
+
+    let a_history = &a.game.game().history;
+    let b_history = &b.game.game().history;
+    let ticks = a_history.len().min(b_history.len());
+
+    assert!(ticks > 90);
+    assert_eq!(a_history[..ticks], b_history[..ticks]);
+
+    assert_eq!(a_history[3], [0, 0]);
+    assert_eq!(a_history[4], [input(0, 4), input(1, 4)]);
+}
+
+#[test]
+fn it_stalls_the_simulation_but_keeps_rendering_while_inputs_are_missing() {
+    let (mut a, mut b) = peers(6, 2);
+
+    for _ in 0..30 {
+        next_frame(&mut a);
+        next_frame(&mut b);
+    }
+
+    assert!(a.game.stalls() > 0);
+    assert!(a.game.current_tick() < a.number_of_updates());
+    assert_eq!(a.number_of_renders(), 30);
+}
+
+#[test]
+fn it_only_asks_for_the_local_input_once_per_tick() {
+    let (transport, _remote) = MemoryTransport::pair(0);
+    let mut lockstep = Lockstep::new(Units::default(), 2, 0, 2, transport);
+
+    let calls = Cell::new(0);
+
+    for _ in 0..5 {
+        lockstep.tick(|| { calls.set(calls.get() + 1); 1 });
+    }
+
+    assert_eq!(lockstep.current_tick(), 2);
+    assert!(lockstep.is_stalled());
+    assert_eq!(lockstep.stalls(), 3);
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn it_drops_inputs_from_players_that_are_not_in_the_game() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut lockstep = Lockstep::new(Units::default(), 2, 0, 0, transport);
+
+    remote.send(InputMessage { player: 2, tick: 0, input: 5 });
+    remote.send(InputMessage { player: usize::MAX, tick: 0, input: 5 });
+
+    assert!(!lockstep.tick(|| 1));
+    assert_eq!(lockstep.current_tick(), 0);
+
+    remote.send(InputMessage { player: 1, tick: 0, input: -1 });
+
+    assert!(lockstep.tick(|| 1));
+    assert_eq!(lockstep.game().history, [[1, -1]]);
+}
+
+#[test]
+fn it_ignores_inputs_for_the_ticks_before_the_first_delayed_input() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut lockstep = Lockstep::new(Units::default(), 2, 0, 2, transport);
+
+    remote.send(InputMessage { player: 1, tick: 0, input: 7 });
+    remote.send(InputMessage { player: 0, tick: 2, input: 7 });
+
+    assert!(lockstep.tick(|| 1));
+    assert_eq!(lockstep.game().history, [[0, 0]]);
+
+    remote.send(InputMessage { player: 1, tick: 2, input: -1 });
+
+    assert!(lockstep.tick(|| 1));
+    assert!(lockstep.tick(|| 1));
+    assert_eq!(lockstep.game().history, [[0, 0], [0, 0], [1, -1]]);
+}
@@ -0,0 +1,248 @@
+use game_loop::{CatchUpPolicy, GameLoop, LoopMode, ManualClock, PacingMode, TimeTrait};
+use std::time::Duration;
+
+const GAME: &str = "fake game";
+
+#[test]
+fn it_sleeps_for_the_rest_of_the_frame_when_renders_are_capped() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.max_renders_per_second = Some(4);
+
+    g.next_frame(|_| {}, |g| g.clock.advance(0.125));
+
+    assert_eq!(g.clock.now(), Duration::from_millis(250));
+
+    g.next_frame(|_| {}, |g| g.clock.advance(0.5));
+
+    assert_eq!(g.clock.now(), Duration::from_millis(750));
+    assert_eq!(g.number_of_updates(), 2);
+}
+
+#[test]
+fn it_does_not_cap_renders_when_the_cap_is_zero() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.max_renders_per_second = Some(0);
+
+    g.next_frame(|_| {}, |g| g.clock.advance(0.125));
+
+    assert_eq!(g.clock.now(), Duration::from_millis(125));
+    assert_eq!(g.number_of_renders(), 1);
+}
+
+#[test]
+fn it_spins_for_the_last_part_of_the_frame_when_using_hybrid_pacing() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), OversleepingClock(ManualClock::new()));
+    g.max_renders_per_second = Some(4);
+    g.pacing = PacingMode::Hybrid { spin_threshold: 0.001953125 };
+
+    assert_eq!(g.spin_threshold(), 0.001953125);
+
+    g.next_frame(|_| {}, |g| g.clock.0.advance(0.125));
+
+    // The first sleep overshoots the frame because the threshold is too low.
+    assert_eq!(g.clock.now(), Duration::from_nanos(263_671_875));
+    assert_eq!(g.spin_threshold(), 0.015625);
+
+    let frame_start = g.clock.now();
+    g.next_frame(|_| {}, |g| g.clock.0.advance(0.125));
+
+    assert_eq!(g.clock.now() - frame_start, Duration::from_millis(250));
+}
+
+#[test]
+fn it_does_not_spin_when_using_sleep_pacing() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), OversleepingClock(ManualClock::new()));
+    g.max_renders_per_second = Some(4);
+
+    g.next_frame(|_| {}, |g| g.clock.0.advance(0.125));
+    g.next_frame(|_| {}, |g| g.clock.0.advance(0.125));
+
+    assert_eq!(g.spin_threshold(), 0.0);
+    assert_eq!(g.clock.now(), Duration::from_nanos(531_250_000));
+}
+
+#[test]
+fn it_reports_the_time_discarded_by_the_maximum_frame_time() {
+    let mut g = GameLoop::with_clock(GAME, 8, 0.5, (), ManualClock::new());
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.discarded_time(), 0.5);
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.discarded_time(), 0.0);
+}
+
+#[test]
+fn it_caps_the_number_of_updates_per_frame_and_keeps_the_remainder() {
+    let mut g = GameLoop::with_clock(GAME, 8, 2.0, (), ManualClock::new());
+    g.catch_up_policy = CatchUpPolicy::KeepRemainder { max_updates: 2 };
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.accumulated_time(), 0.75);
+    assert_eq!(g.discarded_time(), 0.0);
+
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 4);
+}
+
+#[test]
+fn it_caps_the_number_of_updates_per_frame_and_drops_the_remainder() {
+    let mut g = GameLoop::with_clock(GAME, 8, 2.0, (), ManualClock::new());
+    g.catch_up_policy = CatchUpPolicy::DropRemainder { max_updates: 2 };
+
+    g.clock.advance(1.0625);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.accumulated_time(), 0.0625);
+    assert_eq!(g.discarded_time(), 0.75);
+    assert_eq!(g.game_time(), 0.3125);
+
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+}
+
+#[test]
+fn it_slows_game_time_down_smoothly_when_it_falls_behind() {
+    let mut g = GameLoop::with_clock(GAME, 8, 2.0, (), ManualClock::new());
+    g.catch_up_policy = CatchUpPolicy::SlowDown { max_updates: 2, slow_down: 0.5, min_scale: 0.2, recovery: 0.25 };
+
+    g.clock.advance(1.0);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.discarded_time(), 0.0);
+    assert_eq!(g.accumulated_time(), 0.75);
+    assert_eq!(g.catch_up_scale(), 0.5);
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 4);
+    assert_eq!(g.discarded_time(), 0.125);
+    assert_eq!(g.accumulated_time(), 0.625);
+    assert_eq!(g.catch_up_scale(), 0.25);
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.accumulated_time(), 0.4375);
+    assert_eq!(g.catch_up_scale(), 0.2);
+
+    for _ in 0..4 {
+        g.clock.advance(0.25);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    // The backlog was caught up on rather than thrown away.
+    assert_eq!(g.number_of_updates(), 12);
+    assert!((g.accumulated_time() - 0.075).abs() < 1e-9);
+    assert!((g.game_time() - (12.0 * 0.125 + g.accumulated_time())).abs() < 1e-9);
+    assert!((g.catch_up_scale() - 0.95).abs() < 1e-9);
+}
+
+#[test]
+fn it_provides_the_fixed_time_step_as_the_delta_time_in_fixed_mode() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.25);
+    g.next_frame(|g| assert_eq!(g.delta_time(), 0.125), |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+}
+
+#[test]
+fn it_calls_update_once_per_frame_with_a_variable_delta_time() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.loop_mode = LoopMode::Variable;
+
+    let mut deltas = vec![];
+
+    for seconds in [0.3, 0.05, 0.0, 0.7] {
+        g.clock.advance(seconds);
+        g.next_frame(|g| deltas.push(g.delta_time()), |_| {});
+    }
+
+    assert_eq!(deltas, vec![0.3, 0.05, 0.7]);
+    assert_eq!(g.number_of_updates(), 3);
+    assert_eq!(g.number_of_renders(), 4);
+    assert_eq!(g.running_time(), 1.05);
+    assert_eq!(g.blending_factor(), 0.0);
+}
+
+#[test]
+fn it_splits_the_frame_into_substeps_no_larger_than_the_fixed_time_step() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.loop_mode = LoopMode::SemiFixed;
+
+    let mut deltas = vec![];
+
+    g.clock.advance(0.3125);
+    g.next_frame(|g| deltas.push(g.delta_time()), |_| {});
+
+    assert_eq!(deltas, vec![0.125, 0.125, 0.0625]);
+    assert_eq!(g.number_of_updates(), 3);
+    assert_eq!(g.game_time(), 0.3125);
+}
+
+#[test]
+fn it_discards_semi_fixed_substeps_beyond_the_maximum_number_of_updates() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.loop_mode = LoopMode::SemiFixed;
+    g.catch_up_policy = CatchUpPolicy::DropRemainder { max_updates: 2 };
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.number_of_updates(), 2);
+    assert_eq!(g.discarded_time(), 0.25);
+    assert_eq!(g.game_time(), 0.25);
+}
+
+#[test]
+fn it_does_not_call_a_variable_update_while_paused() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.loop_mode = LoopMode::Variable;
+    g.pause();
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| panic!("update called while paused"), |_| {});
+
+    assert_eq!(g.number_of_updates(), 0);
+}
+
+// A clock that always sleeps for 1/64th of a second longer than requested.
+struct OversleepingClock(ManualClock);
+
+impl TimeTrait for OversleepingClock {
+    type Instant = Duration;
+
+    fn now(&self) -> Duration {
+        self.0.now()
+    }
+
+    fn sub(&self, later: &Duration, earlier: &Duration) -> f64 {
+        self.0.sub(later, earlier)
+    }
+
+    fn supports_sleep(&self) -> bool {
+        true
+    }
+
+    fn sleep(&mut self, seconds: f64) {
+        self.0.advance(seconds + 0.015625);
+    }
+
+    fn spin(&mut self, seconds: f64) {
+        self.0.advance(seconds);
+    }
+}
@@ -0,0 +1,73 @@
+use game_loop::{GameLoop, ManualClock};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq)]
+struct Game {
+    position: f64,
+    clones: Rc<Cell<u32>>,
+}
+
+impl Clone for Game {
+    fn clone(&self) -> Self {
+        self.clones.set(self.clones.get() + 1);
+        Self { position: self.position, clones: self.clones.clone() }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clones.set(self.clones.get() + 1);
+        self.position = source.position;
+    }
+}
+
+fn game() -> Game {
+    Game { position: 0.0, clones: Rc::new(Cell::new(0)) }
+}
+
+#[test]
+fn it_renders_with_the_state_from_before_the_most_recent_update() {
+    let mut g = GameLoop::with_clock(game(), 4, 1.0, (), ManualClock::new());
+    let mut rendered = vec![];
+
+    for seconds in [0.375, 0.375, 0.375] {
+        g.clock.advance(seconds);
+        g.next_frame_with_previous(|g| g.game.position += 10.0, |previous, current, blending_factor| {
+            rendered.push((previous.position, current.position, blending_factor));
+        });
+    }
+
+    assert_eq!(rendered, [(0.0, 10.0, 0.5), (20.0, 30.0, 0.0), (30.0, 40.0, 0.5)]);
+}
+
+#[test]
+fn it_renders_the_current_state_twice_before_the_first_update() {
+    let mut g = GameLoop::with_clock(game(), 4, 1.0, (), ManualClock::new());
+    let mut rendered = vec![];
+
+    g.next_frame_with_previous(|_| {}, |previous, current, _| {
+        rendered.push(previous == current);
+    });
+
+    assert_eq!(rendered, [true]);
+    assert!(g.previous_game().is_none());
+}
+
+#[test]
+fn it_only_clones_the_game_on_frames_that_have_updates() {
+    let mut g = GameLoop::with_clock(game(), 4, 1.0, (), ManualClock::new());
+    let clones = g.game.clones.clone();
+
+    for _ in 0..3 {
+        g.clock.advance(0.1);
+        g.next_frame_with_previous(|_| {}, |_, _, _| {});
+    }
+
+    assert_eq!(g.number_of_updates(), 1);
+    assert_eq!(clones.get(), 1);
+
+    g.clock.advance(0.5);
+    g.next_frame_with_previous(|_| {}, |_, _, _| {});
+
+    assert_eq!(g.number_of_updates(), 3);
+    assert_eq!(clones.get(), 3);
+}
@@ -0,0 +1,174 @@
+use game_loop::{replay, GameLoop, InputPlayer, InputRecorder, ManualClock, ReplayInput, TimeAccounting};
+use std::io::ErrorKind;
+use std::time::Duration;
+
+#[derive(Default, Debug, PartialEq)]
+struct Game {
+    position: i64,
+    pending: Vec<i64>,
+    history: Vec<(u64, i64)>,
+}
+
+fn update<T: game_loop::TimeTrait, W>(g: &mut GameLoop<Game, T, W>) {
+    for input in std::mem::take(&mut g.game.pending) {
+        g.game.position += input;
+    }
+
+    g.game.history.push((g.number_of_updates(), g.game.position));
+}
+
+fn new_game_loop(updates_per_second: u32) -> GameLoop<Game, ManualClock, ()> {
+    GameLoop::with_clock(Game::default(), updates_per_second, 1.0, (), ManualClock::new())
+}
+
+#[test]
+fn it_replays_inputs_on_the_same_updates_they_were_recorded_on() {
+    let mut recorder = InputRecorder::new();
+    let mut g = new_game_loop(60);
+
+    for (frame, seconds) in [0.01, 0.05, 0.02, 0.1, 0.0, 0.03, 0.07].iter().enumerate() {
+        // Inputs arrive between frames, e.g. from a window event handler.
+        let input = frame as i64 - 3;
+        recorder.record(g.number_of_updates(), &input).unwrap();
+        g.game.pending.push(input);
+
+        g.clock.advance(*seconds);
+        g.next_frame(update, |_| {});
+    }
+
+    recorder.finish(g.number_of_updates());
+
+    let mut bytes = vec![];
+    recorder.write_to(&mut bytes).unwrap();
+
+    let player = InputPlayer::<i64>::read_from(&bytes[..]).unwrap();
+
+    let replayed = replay(new_game_loop(60), player, |g, inputs| {
+        g.game.pending.extend(inputs);
+        update(g);
+    });
+
+    assert_eq!(replayed.number_of_updates(), g.number_of_updates());
+    assert_eq!(replayed.game.history, g.game.history);
+}
+
+#[test]
+fn it_keeps_replaying_until_the_end_of_the_recorded_session() {
+    let mut recorder = InputRecorder::new();
+    recorder.record(2, &5_i64).unwrap();
+    recorder.finish(100);
+
+    let mut bytes = vec![];
+    recorder.write_to(&mut bytes).unwrap();
+
+    let player = InputPlayer::read_from(&bytes[..]).unwrap();
+    let g = replay(new_game_loop(60), player, |g, inputs: Vec<i64>| {
+        g.game.pending.extend(inputs);
+        update(g);
+    });
+
+    assert_eq!(g.number_of_updates(), 100);
+    assert_eq!(g.game.position, 5);
+    assert_eq!(g.game.history[1], (1, 0));
+    assert_eq!(g.game.history[2], (2, 5));
+}
+
+#[test]
+fn it_groups_inputs_by_update_to_keep_recordings_compact() {
+    let mut recorder = InputRecorder::new();
+
+    for update in 0..100 {
+        recorder.record(update * 3, &1_u8).unwrap();
+        recorder.record(update * 3, &2_u8).unwrap();
+    }
+
+    let mut bytes = vec![];
+    recorder.write_to(&mut bytes).unwrap();
+
+    assert_eq!(recorder.len(), 200);
+    assert_eq!(bytes.len(), 5 + 2 + 100 * 4);
+
+    let mut player = InputPlayer::<u8>::read_from(&bytes[..]).unwrap();
+
+    assert_eq!(player.number_of_updates(), 298);
+    assert_eq!(player.inputs_for(0), vec![1, 2]);
+    assert_eq!(player.inputs_for(1), vec![]);
+    assert_eq!(player.inputs_for(6), vec![1, 2]);
+    assert!(!player.is_finished());
+}
+
+#[test]
+fn it_round_trips_the_built_in_input_types() {
+    let input = (vec![Some(-1_i32), None, Some(i32::MIN)], (String::from("jump"), (1.5_f32, u64::MAX)));
+
+    let mut bytes = vec![];
+    input.encode(&mut bytes);
+
+    let mut slice = &bytes[..];
+    let decoded = <(Vec<Option<i32>>, (String, (f32, u64)))>::decode(&mut slice);
+
+    assert_eq!(decoded, Some(input));
+    assert!(slice.is_empty());
+}
+
+#[test]
+fn it_rejects_recordings_that_are_not_valid() {
+    let error = InputPlayer::<u8>::read_from(&b"not a recording"[..]).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let error = InputPlayer::<u8>::read_from(&b"GLIR\x01\x05\x00\x02\x01"[..]).err().unwrap();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn it_replays_with_the_time_step_and_accounting_of_the_recorded_session() {
+    let mut recorder = InputRecorder::new();
+    recorder.record(1, &3_i64).unwrap();
+    recorder.finish(4);
+
+    let mut bytes = vec![];
+    recorder.write_to(&mut bytes).unwrap();
+
+    let mut g = new_game_loop(60);
+    g.set_time_step(Duration::from_millis(1500));
+    g.set_time_accounting(TimeAccounting::Integer);
+
+    let player = InputPlayer::read_from(&bytes[..]).unwrap();
+    let g = replay(g, player, |g, inputs: Vec<i64>| {
+        assert_eq!(g.delta_time(), 1.5);
+
+        g.game.pending.extend(inputs);
+        update(g);
+    });
+
+    assert_eq!(g.number_of_updates(), 4);
+    assert_eq!(g.time_accounting(), TimeAccounting::Integer);
+    assert_eq!(g.game.history, [(0, 0), (1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn it_returns_an_error_when_inputs_are_recorded_out_of_order() {
+    let mut recorder = InputRecorder::new();
+    recorder.record(5, &1_u8).unwrap();
+
+    let error = recorder.record(4, &2_u8).err().unwrap();
+
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(recorder.len(), 1);
+}
+
+#[test]
+#[cfg(feature = "winit")]
+fn it_round_trips_every_mouse_button() {
+    use game_loop::winit::event::MouseButton;
+    use game_loop::InputEvent;
+
+    for button in [MouseButton::Left, MouseButton::Forward, MouseButton::Other(0), MouseButton::Other(65535)] {
+        let event = InputEvent::MouseButton { button, pressed: true };
+
+        let mut bytes = vec![];
+        event.encode(&mut bytes);
+
+        assert_eq!(InputEvent::decode(&mut &bytes[..]), Some(event));
+    }
+}
@@ -0,0 +1,170 @@
+use game_loop::{GameLoop, InputMessage, ManualClock, MemoryTransport, Rollback, RollbackGame, Transport};
+
+#[derive(Clone, Default, Debug, PartialEq)]
+struct Fighters {
+    positions: [i64; 2],
+    history: Vec<[i64; 2]>,
+}
+
+impl RollbackGame for Fighters {
+    type Input = i64;
+    type Snapshot = Fighters;
+
+    fn advance(&mut self, inputs: &[i64]) {
+        for (position, input) in self.positions.iter_mut().zip(inputs) {
+            *position += input;
+        }
+
+        self.history.push(self.positions);
+    }
+
+    fn save(&self) -> Fighters {
+        self.clone()
+    }
+
+    fn load(&mut self, snapshot: &Fighters) {
+        *self = snapshot.clone();
+    }
+}
+
+// Inputs change every few ticks so that some predictions are wrong.
+fn input(player: usize, tick: u64) -> i64 {
+    ((tick / 4 + tick / 7 + player as u64) % 3) as i64 - 1
+}
+
+type Peer = GameLoop<Rollback<Fighters, MemoryTransport<i64>>, ManualClock, ()>;
+
+fn peers(latency: u64) -> (Peer, Peer) {
+    let (a, b) = MemoryTransport::pair(latency);
+
+    let a = Rollback::new(Fighters::default(), 2, 0, a);
+    let b = Rollback::new(Fighters::default(), 2, 1, b);
+
+    (GameLoop::with_clock(a, 60, 1.0, (), ManualClock::new()),
+     GameLoop::with_clock(b, 60, 1.0, (), ManualClock::new()))
+}
+
+fn next_frame(g: &mut Peer) {
+    g.clock.advance(1.0 / 60.0);
+
+    g.next_frame(|g| {
+        let player = g.game.local_player();
+        let tick = g.game.current_tick();
+
+        g.game.tick(input(player, tick));
+    }, |_| {});
+}
+
+#[test]
+fn it_stays_in_sync_with_the_other_peer_despite_latency() {
+    let (mut a, mut b) = peers(3);
+
+    for _ in 0..120 {
+        next_frame(&mut a);
+        next_frame(&mut b);
+    }
+
+    let mut expected = Fighters::default();
+    for tick in 0..a.game.current_tick() {
+        expected.advance(&[input(0, tick), input(1, tick)]);
+    }
+
+    for g in [&a, &b] {
+        let confirmed = g.game.confirmed_tick() as usize;
+
+        assert!(confirmed > 100);
+        assert_eq!(g.game.game().history[..confirmed], expected.history[..confirmed]);
+    }
+
+    assert!(a.game.rollbacks() > 0);
+    assert!(b.game.rollbacks() > 0);
+}
+
+#[test]
+fn it_rolls_back_and_re_simulates_to_the_present_when_a_late_input_arrives() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut rollback = Rollback::new(Fighters::default(), 2, 0, transport);
+
+    for _ in 0..3 {
+        rollback.tick(1);
+    }
+
+    assert_eq!(rollback.game().positions, [3, 0]);
+
+    remote.send(InputMessage { player: 1, tick: 0, input: -1 });
+    rollback.tick(1);
+
+    assert_eq!(rollback.rollbacks(), 1);
+    assert_eq!(rollback.resimulated_ticks(), 3);
+    assert_eq!(rollback.game().positions, [4, -4]);
+    assert_eq!(rollback.game().history, [[1, -1], [2, -2], [3, -3], [4, -4]]);
+}
+
+#[test]
+fn it_does_not_roll_back_when_the_prediction_was_correct() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut rollback = Rollback::new(Fighters::default(), 2, 0, transport);
+
+    for tick in 0..5 {
+        rollback.tick(1);
+        remote.send(InputMessage { player: 1, tick, input: 0 });
+    }
+
+    rollback.tick(1);
+
+    assert_eq!(rollback.rollbacks(), 0);
+    assert_eq!(rollback.confirmed_tick(), 5);
+}
+
+#[test]
+fn it_drops_inputs_from_players_that_are_not_in_the_game() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut rollback = Rollback::new(Fighters::default(), 2, 0, transport);
+
+    remote.send(InputMessage { player: 2, tick: 0, input: 1 });
+    remote.send(InputMessage { player: usize::MAX, tick: 0, input: 1 });
+
+    assert!(rollback.tick(1));
+    assert_eq!(rollback.rollbacks(), 0);
+    assert_eq!(rollback.game().positions, [1, 0]);
+}
+
+#[test]
+fn it_drops_inputs_that_claim_to_be_from_the_local_player() {
+    let (transport, mut remote) = MemoryTransport::pair(0);
+    let mut rollback = Rollback::new(Fighters::default(), 2, 0, transport);
+
+    for tick in 0..3 {
+        remote.send(InputMessage { player: 0, tick, input: -1 });
+        remote.send(InputMessage { player: 1, tick, input: -1 });
+    }
+
+    assert!(rollback.tick(1));
+    assert_eq!(rollback.rollbacks(), 0);
+    assert_eq!(rollback.confirmed_tick(), 1);
+    assert_eq!(rollback.game().history, [[1, -1]]);
+}
+
+#[test]
+fn it_stalls_when_too_far_ahead_of_the_confirmed_inputs() {
+    let (transport, _remote) = MemoryTransport::pair(0);
+    let mut rollback = Rollback::new(Fighters::default(), 2, 0, transport);
+    rollback.set_max_prediction(4);
+
+    let ticks = (0..6).filter(|_| rollback.tick(1)).count();
+
+    assert_eq!(ticks, 4);
+    assert_eq!(rollback.current_tick(), 4);
+    assert_eq!(rollback.confirmed_tick(), 0);
+}
+
+#[test]
+fn it_delivers_messages_after_the_given_number_of_ticks() {
+    let (mut a, mut b) = MemoryTransport::pair(2);
+
+    a.send(InputMessage { player: 0, tick: 0, input: 7 });
+
+    assert!(b.receive().is_empty());
+    assert!(b.receive().is_empty());
+    assert_eq!(b.receive(), [InputMessage { player: 0, tick: 0, input: 7 }]);
+}
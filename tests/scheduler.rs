@@ -0,0 +1,162 @@
+use game_loop::{GameLoop, ManualClock, Time};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Log = Vec<(&'static str, u64)>;
+
+fn new_game_loop() -> GameLoop<Log, ManualClock, ()> {
+    GameLoop::with_clock(vec![], 8, 10.0, (), ManualClock::new())
+}
+
+fn advance(g: &mut GameLoop<Log, ManualClock, ()>, seconds: f64) {
+    g.clock.advance(seconds);
+    g.next_frame(|_| {}, |_| {});
+}
+
+#[test]
+fn it_runs_a_callback_after_a_delay_in_game_time() {
+    let mut g = new_game_loop();
+
+    g.schedule_after(0.5, |g| { let n = g.number_of_updates(); g.game.push(("after", n)); });
+
+    advance(&mut g, 0.375);
+    assert!(g.game.is_empty());
+
+    advance(&mut g, 0.375);
+    assert_eq!(g.game, vec![("after", 4)]);
+
+    advance(&mut g, 1.0);
+    assert_eq!(g.game.len(), 1);
+}
+
+#[test]
+fn it_runs_a_callback_at_an_absolute_game_time() {
+    let mut g = new_game_loop();
+
+    g.schedule_at(0.25, |g| { let n = g.number_of_updates(); g.game.push(("at", n)); });
+    advance(&mut g, 1.0);
+
+    assert_eq!(g.game, vec![("at", 2)]);
+}
+
+#[test]
+fn it_runs_a_callback_every_n_seconds() {
+    let mut g = new_game_loop();
+
+    g.schedule_every(0.25, |g| { let n = g.number_of_updates(); g.game.push(("every", n)); });
+    advance(&mut g, 1.0);
+
+    assert_eq!(g.game, vec![("every", 2), ("every", 4), ("every", 6), ("every", 8)]);
+}
+
+#[test]
+fn it_runs_a_callback_every_n_updates() {
+    let mut g = new_game_loop();
+
+    advance(&mut g, 0.125);
+    g.schedule_every_updates(3, |g| { let n = g.number_of_updates(); g.game.push(("updates", n)); });
+    advance(&mut g, 1.0);
+
+    assert_eq!(g.game, vec![("updates", 4), ("updates", 7)]);
+}
+
+#[test]
+fn it_fires_timers_in_the_order_they_were_due() {
+    let mut g = new_game_loop();
+
+    g.schedule_every_updates(1, |g| g.game.push(("updates", 0)));
+    g.schedule_after(0.1, |g| g.game.push(("second", 0)));
+    g.schedule_after(0.05, |g| g.game.push(("first", 0)));
+    g.schedule_after(0.1, |g| g.game.push(("third", 0)));
+
+    advance(&mut g, 0.125);
+
+    assert_eq!(g.game, vec![("first", 0), ("second", 0), ("third", 0), ("updates", 0)]);
+}
+
+#[test]
+fn it_can_cancel_timers() {
+    let mut g = new_game_loop();
+
+    let once = g.schedule_after(0.5, |g| g.game.push(("once", 0)));
+    let every = g.schedule_every_updates(1, |g| g.game.push(("every", 0)));
+
+    assert!(g.is_scheduled(once));
+    assert!(g.cancel_timer(once));
+    assert!(!g.cancel_timer(once));
+    assert!(!g.is_scheduled(once));
+
+    advance(&mut g, 0.25);
+    assert_eq!(g.game.len(), 2);
+
+    g.cancel_timer(every);
+    advance(&mut g, 1.0);
+
+    assert_eq!(g.game.len(), 2);
+}
+
+#[test]
+fn it_can_cancel_a_repeating_timer_from_its_own_callback() {
+    let mut g = new_game_loop();
+    let timer_id = Arc::new(Mutex::new(None));
+    let id = timer_id.clone();
+
+    *timer_id.lock().unwrap() = Some(g.schedule_every(0.125, move |g| {
+        let n = g.number_of_updates();
+        g.game.push(("every", n));
+
+        if n == 2 { g.cancel_timer(id.lock().unwrap().unwrap()); }
+    }));
+
+    advance(&mut g, 1.0);
+
+    assert_eq!(g.game, vec![("every", 1), ("every", 2)]);
+    assert!(!g.is_scheduled(timer_id.lock().unwrap().unwrap()));
+}
+
+#[test]
+fn it_does_not_fire_timers_while_paused() {
+    let mut g = new_game_loop();
+
+    g.schedule_after(0.25, |g| g.game.push(("after", 0)));
+    g.pause();
+
+    advance(&mut g, 1.0);
+    assert!(g.game.is_empty());
+
+    g.resume();
+
+    advance(&mut g, 0.25);
+    assert_eq!(g.game.len(), 1);
+}
+
+#[test]
+fn it_scales_timers_with_the_time_scale() {
+    let mut g = new_game_loop();
+
+    g.schedule_after(0.5, |g| g.game.push(("after", 0)));
+    g.time_scale = 0.5;
+
+    advance(&mut g, 0.75);
+    assert!(g.game.is_empty());
+
+    advance(&mut g, 0.25);
+    assert_eq!(g.game.len(), 1);
+}
+
+#[test]
+fn it_can_move_a_game_loop_with_timers_to_another_thread() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let g = GameLoop::<u32, Time, ()>::new(0, 60, 0.1, ());
+    assert_send(&g);
+
+    let mut g = new_game_loop();
+    g.schedule_after(0.25, |g| g.game.push(("after", 0)));
+    g.add_channel("ai", 4, |g| g.game.push(("ai", 0)));
+    assert_send(&g);
+
+    let g = thread::spawn(move || { advance(&mut g, 0.25); g }).join().unwrap();
+
+    assert_eq!(g.game, vec![("after", 0), ("ai", 0)]);
+}
@@ -0,0 +1,84 @@
+use game_loop::{FrameStats, GameLoop, ManualClock};
+
+const GAME: &str = "fake game";
+
+#[test]
+fn it_reports_the_average_fps_and_ups_over_the_window() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    for _ in 0..4 {
+        g.clock.advance(0.25);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    assert_eq!(g.stats().len(), 4);
+    assert_eq!(g.stats().fps(), 4.0);
+    assert_eq!(g.stats().ups(), 8.0);
+    assert_eq!(g.stats().mean_frame_time(), 0.25);
+}
+
+#[test]
+fn it_only_keeps_the_most_recent_frames_in_the_window() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.stats_mut().set_window(2);
+
+    for seconds in [0.5, 0.125, 0.25] {
+        g.clock.advance(seconds);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    assert_eq!(g.stats().len(), 2);
+    assert_eq!(g.stats().total_time(), 0.375);
+    assert_eq!(g.stats().max_frame_time(), 0.25);
+}
+
+#[test]
+fn it_reports_min_max_and_percentile_frame_times() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.stats_mut().set_window(100);
+
+    for i in 1..=100 {
+        g.clock.advance(i as f64 / 1000.0);
+        g.next_frame(|_| {}, |_| {});
+    }
+
+    let stats = g.stats();
+
+    assert_eq!(stats.min_frame_time(), 0.001);
+    assert_eq!(stats.max_frame_time(), 0.1);
+    assert_eq!(stats.p50_frame_time(), 0.05);
+    assert_eq!(stats.p95_frame_time(), 0.095);
+    assert_eq!(stats.p99_frame_time(), 0.099);
+}
+
+#[test]
+fn it_does_not_count_frames_that_were_not_rendered_towards_the_fps() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.window_occluded = true;
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |_| {});
+
+    assert_eq!(g.stats().fps(), 0.0);
+    assert_eq!(g.stats().ups(), 8.0);
+}
+
+#[test]
+fn it_can_query_the_stats_from_inside_the_closures() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |_| {});
+
+    g.clock.advance(0.5);
+    g.next_frame(|_| {}, |g| assert_eq!(g.stats().fps(), 2.0));
+}
+
+#[test]
+fn it_reports_zero_for_an_empty_window() {
+    let stats = FrameStats::new(10);
+
+    assert!(stats.is_empty());
+    assert_eq!(stats.fps(), 0.0);
+    assert_eq!(stats.p99_frame_time(), 0.0);
+}
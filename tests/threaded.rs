@@ -0,0 +1,103 @@
+use game_loop::{triple_buffer, ThreadedGameLoop};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread;
+
+#[test]
+fn it_reads_the_most_recently_published_value() {
+    let (mut writer, mut reader) = triple_buffer(0);
+
+    assert!(!reader.update());
+    assert_eq!(reader.read(), &0);
+
+    for value in 1..=3 {
+        *writer.write() = value;
+        writer.publish();
+    }
+
+    assert!(reader.has_update());
+    assert!(reader.update());
+    assert_eq!(reader.read(), &3);
+
+    assert!(!reader.has_update());
+    assert!(!reader.update());
+    assert_eq!(reader.read(), &3);
+}
+
+#[test]
+fn it_hands_values_between_threads_without_going_backwards() {
+    let (mut writer, mut reader) = triple_buffer(0_u64);
+
+    let producer = thread::spawn(move || {
+        for value in 1..=100_000 {
+            *writer.write() = value;
+            writer.publish();
+        }
+    });
+
+    let mut latest = 0;
+
+    while latest < 100_000 {
+        if reader.update() {
+            assert!(*reader.read() > latest);
+            latest = *reader.read();
+        }
+
+        thread::yield_now();
+    }
+
+    producer.join().unwrap();
+}
+
+#[test]
+fn it_updates_on_another_thread_until_the_simulation_exits() {
+    let render_thread = thread::current().id();
+
+    let mut g = ThreadedGameLoop::new((0, None), 240, 0.1, |g| {
+        g.game.0 += 1;
+        g.game.1 = Some(thread::current().id());
+
+        if g.game.0 == 10 { g.exit(); }
+    }, |game| game.0);
+
+    let mut rendered = vec![];
+
+    while g.next_frame(|previous, current, blending_factor| {
+        assert!(previous <= current);
+        assert!((0.0..=1.0).contains(&blending_factor));
+        rendered.push(*current);
+    }) {
+        thread::yield_now();
+    }
+
+    assert!(!g.is_running());
+    assert_eq!(g.number_of_renders(), rendered.len() as u64);
+    assert!(rendered.windows(2).all(|w| w[0] <= w[1]));
+
+    let (updates, update_thread) = g.join();
+
+    assert_eq!(updates, 10);
+    assert_ne!(update_thread, Some(render_thread));
+}
+
+#[test]
+fn it_stops_the_simulation_when_joined() {
+    let g = ThreadedGameLoop::new(0_u64, 240, 0.1, |g| g.game += 1, |game| *game);
+
+    assert!(g.is_running());
+    g.join();
+}
+
+#[test]
+fn it_stops_running_when_the_update_panics() {
+    let mut g = ThreadedGameLoop::new(0, 240, 0.1, |g| {
+        g.game += 1;
+        if g.game == 3 { panic!("update failed"); }
+    }, |game| *game);
+
+    while g.next_frame(|_, _, _| {}) {
+        thread::yield_now();
+    }
+
+    assert!(!g.is_running());
+    assert!(catch_unwind(AssertUnwindSafe(|| g.join())).is_err());
+}
@@ -0,0 +1,77 @@
+use game_loop::{GameLoop, ManualClock, Time};
+use std::thread::sleep;
+use std::time::Duration;
+
+const GAME: &str = "fake game";
+
+#[test]
+fn it_measures_how_long_each_update_and_the_render_took() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.measure_phases = true;
+
+    g.clock.advance(0.25);
+    g.next_frame(|_| sleep(Duration::from_millis(5)), |_| sleep(Duration::from_millis(20)));
+
+    assert_eq!(g.number_of_updates(), 2);
+
+    assert!(g.last_update_time() >= 0.005);
+    assert!(g.last_update_time() < g.update_time());
+    assert!(g.update_time() >= 0.01);
+    assert!(g.render_time() >= 0.02);
+}
+
+#[test]
+fn it_tells_apart_slow_updates_from_slow_renders() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.measure_phases = true;
+
+    g.clock.advance(0.125);
+    g.next_frame(|_| {}, |_| sleep(Duration::from_millis(20)));
+
+    assert!(g.render_time() > g.update_time());
+
+    g.clock.advance(0.125);
+    g.next_frame(|_| sleep(Duration::from_millis(20)), |_| {});
+
+    assert!(g.update_time() > g.render_time());
+}
+
+#[test]
+fn it_does_not_count_time_waiting_for_the_next_render_as_render_time() {
+    let mut g: GameLoop<_, Time, _> = GameLoop::new(GAME, 100, 1.0, ());
+    g.measure_phases = true;
+    g.max_renders_per_second = Some(20);
+
+    g.next_frame(|_| {}, |_| {});
+
+    assert!(g.sleep_time() >= 0.04);
+    assert!(g.render_time() < g.sleep_time());
+}
+
+#[test]
+fn it_keeps_the_average_phase_times_in_the_frame_stats() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+    g.measure_phases = true;
+
+    for _ in 0..2 {
+        g.clock.advance(0.125);
+        g.next_frame(|_| {}, |_| sleep(Duration::from_millis(10)));
+    }
+
+    assert!(g.stats().mean_render_time() >= 0.01);
+    assert!(g.stats().mean_update_time() < g.stats().mean_render_time());
+    assert!(g.stats().mean_sleep_time() < g.stats().mean_render_time());
+}
+
+#[test]
+fn it_does_not_measure_phases_unless_asked_to() {
+    let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+    g.clock.advance(0.125);
+    g.next_frame(|_| sleep(Duration::from_millis(5)), |_| sleep(Duration::from_millis(5)));
+
+    assert_eq!(g.last_update_time(), 0.0);
+    assert_eq!(g.update_time(), 0.0);
+    assert_eq!(g.render_time(), 0.0);
+    assert_eq!(g.sleep_time(), 0.0);
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "tracing")]
+
+use game_loop::{GameLoop, ManualClock};
+use std::sync::{Arc, Mutex};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+const GAME: &str = "fake game";
+
+#[test]
+fn it_emits_spans_for_each_frame_update_and_render() {
+    let names = record(|| {
+        let mut g = GameLoop::with_clock(GAME, 8, 1.0, (), ManualClock::new());
+
+        g.clock.advance(0.25);
+        g.next_frame(|_| {}, |_| {});
+    });
+
+    assert_eq!(names, ["next_frame", "update", "update", "render"]);
+}
+
+#[test]
+fn it_emits_events_for_clamped_frames_and_occluded_sleeps() {
+    let names = record(|| {
+        let mut g = GameLoop::with_clock(GAME, 8, 0.5, (), ManualClock::new());
+        g.window_occluded = true;
+
+        g.clock.advance(1.0);
+        g.next_frame(|_| {}, |_| {});
+    });
+
+    assert!(names.contains(&"event frame_time".to_string()));
+    assert!(names.contains(&"event sleep_time".to_string()));
+    assert!(!names.contains(&"render".to_string()));
+}
+
+fn record<F: FnOnce()>(f: F) -> Vec<String> {
+    let recorder = Recorder::default();
+    let names = recorder.names.clone();
+
+    tracing::subscriber::with_default(recorder, f);
+
+    let names = names.lock().unwrap().clone();
+    names
+}
+
+// Records the names of spans and the first field of events after the message.
+#[derive(Default)]
+struct Recorder {
+    names: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut names = self.names.lock().unwrap();
+        names.push(span.metadata().name().to_string());
+
+        Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let field = event.metadata().fields().iter().find(|f| f.name() != "message").unwrap();
+        self.names.lock().unwrap().push(format!("event {}", field.name()));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}